@@ -0,0 +1,144 @@
+//! Argument parsing and output formatting for `amm-cli`, kept apart from the
+//! RPC calls in `main.rs` so both can be tested without a validator.
+
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use amm::state::Pool;
+use amm_client::accounts;
+use amm_client::quote::PoolReserves;
+use clap::{Parser, Subcommand};
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+
+#[derive(Parser)]
+#[command(name = "amm-cli", version, about = "Operate Constant Product AMM pools")]
+pub struct Cli {
+    /// RPC endpoint
+    #[arg(long, short = 'u', global = true, env = "AMM_RPC_URL", default_value = "http://127.0.0.1:8899")]
+    pub url: String,
+    
+    /// Fee payer and signer keypair file [default: ~/.config/solana/id.json]
+    #[arg(long, short = 'k', global = true, env = "AMM_KEYPAIR")]
+    pub keypair: Option<PathBuf>,
+    
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Create the global config with the signer as admin
+    InitConfig,
+    /// Create vaults and an LP mint for a token pair and register the pool (admin only)
+    CreatePool {
+        #[arg(long)]
+        mint_a: Pubkey,
+        #[arg(long)]
+        mint_b: Pubkey,
+        /// Decimals of the new LP mint
+        #[arg(long, default_value_t = 9)]
+        lp_decimals: u8,
+    },
+    /// Deposit both tokens; the deposit is trimmed to the pool ratio
+    Add {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        amount_a: u64,
+        #[arg(long)]
+        amount_b: u64,
+        /// Allowed shortfall against the quote, in basis points
+        #[arg(long, default_value_t = 50)]
+        slippage_bps: u16,
+    },
+    /// Burn LP tokens for both tokens
+    Remove {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        lp_amount: u64,
+        /// Allowed shortfall against the quote, in basis points
+        #[arg(long, default_value_t = 50)]
+        slippage_bps: u16,
+    },
+    /// Swap an exact input amount
+    Swap {
+        #[arg(long)]
+        pool: Pubkey,
+        /// Mint of the token paid in
+        #[arg(long)]
+        input_mint: Pubkey,
+        #[arg(long)]
+        amount_in: u64,
+        /// Allowed shortfall against the quote, in basis points
+        #[arg(long, default_value_t = 50)]
+        slippage_bps: u16,
+    },
+    /// Quote a swap without sending a transaction
+    Quote {
+        #[arg(long)]
+        pool: Pubkey,
+        #[arg(long)]
+        input_mint: Pubkey,
+        #[arg(long)]
+        amount_in: u64,
+    },
+    /// Print a pool's state and reserves
+    ShowPool {
+        #[arg(long)]
+        pool: Pubkey,
+    },
+    /// List every pool owned by the program
+    ListPools,
+}
+
+/// Print a pool's state and reserves
+pub fn write_pool(out: &mut impl Write, address: &Pubkey, pool: &Pool, reserves: &PoolReserves) -> io::Result<()> {
+    writeln!(out, "Pool: {address}")?;
+    writeln!(out, "Token A: {} (vault {}, reserve {})", pool.token_mint_a, pool.vault_a, reserves.reserve_a)?;
+    writeln!(out, "Token B: {} (vault {}, reserve {})", pool.token_mint_b, pool.vault_b, reserves.reserve_b)?;
+    writeln!(out, "LP Mint: {} (supply {})", pool.lp_mint, reserves.lp_supply)?;
+    writeln!(out, "Fee: {} bps", pool.fee_bps)?;
+    writeln!(out, "Emergency mode: {}", pool.is_emergency_mode())
+}
+
+/// Print one line per pool account. Accounts that do not decode as a current
+/// `Pool`, such as pools not yet grown by `migrate_pool`, are skipped with a
+/// line on `warnings` so one bad account does not hide the rest.
+pub fn write_pool_list(out: &mut impl Write, warnings: &mut impl Write, pools: &[(Pubkey, Account)]) -> io::Result<()> {
+    let mut listed = 0;
+    for (address, account) in pools {
+        match accounts::deserialize_pool(&account.data) {
+            Ok(state) => {
+                writeln!(
+                    out,
+                    "{address}  {} / {}  fee {} bps{}",
+                    state.token_mint_a,
+                    state.token_mint_b,
+                    state.fee_bps,
+                    if state.is_emergency_mode() { "  [emergency]" } else { "" }
+                )?;
+                listed += 1;
+            }
+            Err(_) if account.data.len() < Pool::LEN => {
+                writeln!(warnings, "warning: skipping {address}: older pool layout, run migrate_pool")?;
+            }
+            Err(error) => {
+                writeln!(warnings, "warning: skipping {address}: {error}")?;
+            }
+        }
+    }
+    
+    let skipped = pools.len() - listed;
+    if skipped > 0 {
+        writeln!(out, "{listed} pool(s), {skipped} skipped")
+    } else {
+        writeln!(out, "{listed} pool(s)")
+    }
+}
+
+/// Reduce a quoted amount by `slippage_bps`
+pub fn apply_slippage(amount: u64, slippage_bps: u16) -> u64 {
+    ((amount as u128) * (10_000u128.saturating_sub(slippage_bps as u128)) / 10_000) as u64
+}
@@ -0,0 +1,229 @@
+//! Command-line tool for operating AMM pools.
+//!
+//! Amounts are raw token base units. Every command reads the fee payer from
+//! `--keypair` and talks to `--url`, which defaults to a local test validator.
+
+use std::io;
+use std::path::PathBuf;
+
+use amm::state::Pool;
+use amm_cli::{apply_slippage, write_pool, write_pool_list, Cli, Command};
+use amm_client::instructions::{self, args};
+use amm_client::quote::{self, PoolReserves};
+use amm_client::{accounts, pda, PoolKeys};
+use anchor_lang::Discriminator;
+use anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{spl_token, Mint, TokenAccount};
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{read_keypair_file, Keypair, Signature, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let rpc = RpcClient::new_with_commitment(cli.url.clone(), CommitmentConfig::confirmed());
+    
+    match cli.command {
+        Command::InitConfig => {
+            let payer = load_keypair(&cli.keypair)?;
+            let signature = send(&rpc, &payer, &[instructions::initialize_config(&payer.pubkey())], &[])?;
+            println!("Config: {}", pda::find_config_address().0);
+            println!("Admin: {}", payer.pubkey());
+            println!("Signature: {signature}");
+        }
+        Command::CreatePool { mint_a, mint_b, lp_decimals } => {
+            let payer = load_keypair(&cli.keypair)?;
+            create_pool(&rpc, &payer, mint_a, mint_b, lp_decimals)?;
+        }
+        Command::Add { pool, amount_a, amount_b, slippage_bps } => {
+            let payer = load_keypair(&cli.keypair)?;
+            let (state, reserves) = load_pool(&rpc, &pool)?;
+            let keys = PoolKeys::new(pool, &state);
+            let deposit = quote::quote_add_liquidity(&state, &reserves, amount_a, amount_b)?;
+            
+            let ix = instructions::add_liquidity(&payer.pubkey(), &keys, args::AddLiquidity {
+                amount_a_max: amount_a,
+                amount_b_max: amount_b,
+                amount_a_min: apply_slippage(deposit.amount_a, slippage_bps),
+                amount_b_min: apply_slippage(deposit.amount_b, slippage_bps),
+                min_lp_tokens: apply_slippage(deposit.lp_tokens, slippage_bps),
+                deadline: None,
+            });
+            let signature = send(&rpc, &payer, &[ix], &[])?;
+            println!(
+                "Deposited {} A + {} B for ~{} LP",
+                deposit.amount_a, deposit.amount_b, deposit.lp_tokens
+            );
+            println!("Signature: {signature}");
+        }
+        Command::Remove { pool, lp_amount, slippage_bps } => {
+            let payer = load_keypair(&cli.keypair)?;
+            let (state, reserves) = load_pool(&rpc, &pool)?;
+            let keys = PoolKeys::new(pool, &state);
+            let (amount_a, amount_b) = quote::quote_remove_liquidity(&reserves, lp_amount)?;
+            
+            let ix = instructions::remove_liquidity(&payer.pubkey(), &keys, args::RemoveLiquidity {
+                lp_amount,
+                min_amount_a: apply_slippage(amount_a, slippage_bps),
+                min_amount_b: apply_slippage(amount_b, slippage_bps),
+                deadline: None,
+            });
+            let signature = send(&rpc, &payer, &[ix], &[])?;
+            println!("Withdrew ~{amount_a} A + ~{amount_b} B for {lp_amount} LP");
+            println!("Signature: {signature}");
+        }
+        Command::Swap { pool, input_mint, amount_in, slippage_bps } => {
+            let payer = load_keypair(&cli.keypair)?;
+            let (state, reserves) = load_pool(&rpc, &pool)?;
+            let keys = PoolKeys::new(pool, &state);
+            let swap = quote::quote_swap(&state, &reserves, &input_mint, amount_in, 0)?;
+            
+            let ix = instructions::swap(&payer.pubkey(), &keys, &input_mint, None, args::Swap {
+                amount_in,
+                minimum_amount_out: apply_slippage(swap.amount_out, slippage_bps),
+                referral_bps: 0,
+                deadline: None,
+            });
+            let signature = send(&rpc, &payer, &[ix], &[])?;
+            println!("Swapped {amount_in} for ~{}", swap.amount_out);
+            println!("Signature: {signature}");
+        }
+        Command::Quote { pool, input_mint, amount_in } => {
+            let (state, reserves) = load_pool(&rpc, &pool)?;
+            let swap = quote::quote_swap(&state, &reserves, &input_mint, amount_in, 0)?;
+            println!("Amount out: {}", swap.amount_out);
+            println!("LP fee: {}", swap.fee_amount);
+            println!("Price impact: {} bps", swap.price_impact_bps);
+        }
+        Command::ShowPool { pool } => {
+            let (state, reserves) = load_pool(&rpc, &pool)?;
+            write_pool(&mut io::stdout(), &pool, &state, &reserves)?;
+        }
+        Command::ListPools => {
+            let config = RpcProgramAccountsConfig {
+                filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                    0,
+                    Pool::DISCRIMINATOR.to_vec(),
+                ))]),
+                account_config: RpcAccountInfoConfig {
+                    encoding: Some(solana_account_decoder::UiAccountEncoding::Base64),
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+            let pools = rpc.get_program_accounts_with_config(&amm::ID, config)?;
+            write_pool_list(&mut io::stdout(), &mut io::stderr(), &pools)?;
+        }
+    }
+    
+    Ok(())
+}
+
+fn load_keypair(path: &Option<PathBuf>) -> Result<Keypair> {
+    let path = match path {
+        Some(path) => path.clone(),
+        None => PathBuf::from(std::env::var("HOME").context("HOME is not set")?).join(".config/solana/id.json"),
+    };
+    read_keypair_file(&path).map_err(|e| anyhow!("failed to read keypair {}: {e}", path.display()))
+}
+
+fn send(rpc: &RpcClient, payer: &Keypair, ixs: &[Instruction], signers: &[&Keypair]) -> Result<Signature> {
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+    let tx = Transaction::new_signed_with_payer(
+        ixs,
+        Some(&payer.pubkey()),
+        &all_signers,
+        rpc.get_latest_blockhash()?,
+    );
+    Ok(rpc.send_and_confirm_transaction(&tx)?)
+}
+
+/// Fetch a pool and the balances of its vaults and LP mint
+fn load_pool(rpc: &RpcClient, address: &Pubkey) -> Result<(Pool, PoolReserves)> {
+    let data = rpc.get_account_data(address).context("pool account not found")?;
+    let pool = accounts::deserialize_pool(&data)?;
+    
+    let [vault_a, vault_b, lp_mint] = rpc
+        .get_multiple_accounts(&[pool.vault_a, pool.vault_b, pool.lp_mint])?
+        .try_into()
+        .map_err(|_| anyhow!("unexpected RPC response"))?;
+    let vault_a: TokenAccount = accounts::deserialize(&vault_a.context("vault A not found")?.data)?;
+    let vault_b: TokenAccount = accounts::deserialize(&vault_b.context("vault B not found")?.data)?;
+    let lp_mint: Mint = accounts::deserialize(&lp_mint.context("LP mint not found")?.data)?;
+    
+    let reserves = PoolReserves {
+        reserve_a: vault_a.amount,
+        reserve_b: vault_b.amount,
+        lp_supply: lp_mint.supply,
+    };
+    Ok((pool, reserves))
+}
+
+fn create_pool(rpc: &RpcClient, payer: &Keypair, mint_x: Pubkey, mint_y: Pubkey, lp_decimals: u8) -> Result<()> {
+    let (token_mint_a, token_mint_b) = pda::sort_mints(mint_x, mint_y);
+    let (pool, _) = pda::find_pool_address(&token_mint_a, &token_mint_b);
+    
+    // Vaults and the LP mint are plain keypair accounts with the pool PDA as authority
+    let vault_a = Keypair::new();
+    let vault_b = Keypair::new();
+    let lp_mint = Keypair::new();
+    let account_rent = rpc.get_minimum_balance_for_rent_exemption(spl_token::state::Account::LEN)?;
+    let mint_rent = rpc.get_minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN)?;
+    
+    let mut ixs = Vec::new();
+    for (vault, mint) in [(&vault_a, &token_mint_a), (&vault_b, &token_mint_b)] {
+        ixs.push(system_instruction::create_account(
+            &payer.pubkey(),
+            &vault.pubkey(),
+            account_rent,
+            spl_token::state::Account::LEN as u64,
+            &spl_token::ID,
+        ));
+        ixs.push(spl_token::instruction::initialize_account3(&spl_token::ID, &vault.pubkey(), mint, &pool)?);
+    }
+    ixs.push(system_instruction::create_account(
+        &payer.pubkey(),
+        &lp_mint.pubkey(),
+        mint_rent,
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::ID,
+    ));
+    ixs.push(spl_token::instruction::initialize_mint2(&spl_token::ID, &lp_mint.pubkey(), &pool, None, lp_decimals)?);
+    
+    let keys = PoolKeys {
+        pool,
+        token_mint_a,
+        token_mint_b,
+        vault_a: vault_a.pubkey(),
+        vault_b: vault_b.pubkey(),
+        lp_mint: lp_mint.pubkey(),
+    };
+    ixs.push(instructions::initialize_pool(&payer.pubkey(), &keys));
+    
+    // The signer's LP account, so `add` works straight away
+    ixs.push(create_associated_token_account_idempotent(
+        &payer.pubkey(),
+        &payer.pubkey(),
+        &keys.lp_mint,
+        &spl_token::ID,
+    ));
+    
+    let signature = send(rpc, payer, &ixs, &[&vault_a, &vault_b, &lp_mint])?;
+    println!("Pool: {pool}");
+    println!("Vault A: {}", keys.vault_a);
+    println!("Vault B: {}", keys.vault_b);
+    println!("LP Mint: {}", keys.lp_mint);
+    println!("Your LP account: {}", get_associated_token_address(&payer.pubkey(), &keys.lp_mint));
+    println!("Signature: {signature}");
+    Ok(())
+}
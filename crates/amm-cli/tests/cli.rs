@@ -0,0 +1,112 @@
+//! Argument parsing and output of the commands that need no RPC.
+
+use amm::state::Pool;
+use amm_cli::{apply_slippage, write_pool_list, Cli, Command};
+use anchor_lang::Discriminator;
+use clap::Parser;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+
+fn pool_account(pool: &Pool) -> Account {
+    let mut data = Pool::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(pool));
+    Account {
+        data,
+        owner: amm::ID,
+        ..Account::default()
+    }
+}
+
+fn pool(fee_bps: u16) -> Pool {
+    let mut pool: Pool = bytemuck::Zeroable::zeroed();
+    pool.token_mint_a = Pubkey::new_unique();
+    pool.token_mint_b = Pubkey::new_unique();
+    pool.fee_bps = fee_bps;
+    pool
+}
+
+#[test]
+fn parses_amounts_and_defaults() {
+    let pool = Pubkey::new_unique();
+    let cli = Cli::try_parse_from([
+        "amm-cli",
+        "add",
+        "--pool",
+        &pool.to_string(),
+        "--amount-a",
+        "1000",
+        "--amount-b",
+        "2000",
+    ])
+    .unwrap();
+
+    assert_eq!(cli.url, "http://127.0.0.1:8899");
+    assert!(cli.keypair.is_none());
+    let Command::Add { pool: parsed, amount_a, amount_b, slippage_bps } = cli.command else {
+        panic!("expected the add command");
+    };
+    assert_eq!((parsed, amount_a, amount_b, slippage_bps), (pool, 1_000, 2_000, 50));
+}
+
+#[test]
+fn global_options_follow_the_subcommand() {
+    let cli = Cli::try_parse_from(["amm-cli", "list-pools", "-u", "https://rpc.example", "-k", "/tmp/id.json"]).unwrap();
+    assert_eq!(cli.url, "https://rpc.example");
+    assert_eq!(cli.keypair.unwrap().to_str(), Some("/tmp/id.json"));
+    assert!(matches!(cli.command, Command::ListPools));
+}
+
+#[test]
+fn rejects_malformed_arguments() {
+    assert!(Cli::try_parse_from(["amm-cli", "show-pool", "--pool", "not-a-pubkey"]).is_err());
+    assert!(Cli::try_parse_from(["amm-cli", "swap", "--pool", &Pubkey::new_unique().to_string()]).is_err());
+    let negative = ["amm-cli", "remove", "--pool", &Pubkey::new_unique().to_string(), "--lp-amount", "-1"];
+    assert!(Cli::try_parse_from(negative).is_err());
+}
+
+#[test]
+fn list_pools_skips_what_it_cannot_decode() {
+    let current = pool(30);
+    let mut emergency = pool(5);
+    emergency.emergency_mode = 1;
+    let (first, legacy, garbage, last) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+    // A pool still in the layout before `migrate_pool`, and one of the right
+    // size that is not a pool at all
+    let mut legacy_account = pool_account(&current);
+    legacy_account.data.truncate(8 + 164);
+    let mut garbage_account = pool_account(&current);
+    garbage_account.data[..8].fill(0);
+
+    let pools = vec![
+        (first, pool_account(&current)),
+        (legacy, legacy_account),
+        (garbage, garbage_account),
+        (last, pool_account(&emergency)),
+    ];
+    let (mut out, mut warnings) = (vec![], vec![]);
+    write_pool_list(&mut out, &mut warnings, &pools).unwrap();
+
+    let out = String::from_utf8(out).unwrap();
+    assert_eq!(
+        out,
+        format!(
+            "{first}  {} / {}  fee 30 bps\n{last}  {} / {}  fee 5 bps  [emergency]\n2 pool(s), 2 skipped\n",
+            current.token_mint_a, current.token_mint_b, emergency.token_mint_a, emergency.token_mint_b,
+        )
+    );
+    let warnings = String::from_utf8(warnings).unwrap();
+    let warnings: Vec<_> = warnings.lines().collect();
+    assert_eq!(warnings.len(), 2);
+    assert_eq!(warnings[0], format!("warning: skipping {legacy}: older pool layout, run migrate_pool"));
+    assert!(warnings[1].starts_with(&format!("warning: skipping {garbage}: ")));
+}
+
+#[test]
+fn slippage_lowers_the_minimum() {
+    assert_eq!(apply_slippage(1_000_000, 50), 995_000);
+    assert_eq!(apply_slippage(1_000_000, 0), 1_000_000);
+    assert_eq!(apply_slippage(u64::MAX, 10_000), 0);
+    assert_eq!(apply_slippage(999, 50), 994);
+}
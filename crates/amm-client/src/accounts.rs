@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use amm::state::{Config, Farm, LpPosition, Pool, UserStake};
+
+/// Deserialize raw account data, checking the Anchor discriminator
+pub fn deserialize<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    let mut data = data;
+    T::try_deserialize(&mut data)
+}
+
+/// Deserialize a `Pool` account. Zero-copy decoding panics on any other
+/// length, so pools not yet grown by `migrate_pool` are rejected first.
+pub fn deserialize_pool(data: &[u8]) -> Result<Pool> {
+    require!(data.len() == Pool::LEN, ErrorCode::AccountDidNotDeserialize);
+    deserialize(data)
+}
+
+/// Deserialize the `Config` account
+pub fn deserialize_config(data: &[u8]) -> Result<Config> {
+    deserialize(data)
+}
+
+/// Deserialize a `Farm` account
+pub fn deserialize_farm(data: &[u8]) -> Result<Farm> {
+    deserialize(data)
+}
+
+/// Deserialize a `UserStake` account
+pub fn deserialize_user_stake(data: &[u8]) -> Result<UserStake> {
+    deserialize(data)
+}
+
+/// Deserialize an `LpPosition` account
+pub fn deserialize_position(data: &[u8]) -> Result<LpPosition> {
+    deserialize(data)
+}
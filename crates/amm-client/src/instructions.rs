@@ -0,0 +1,622 @@
+//! Instruction builders.
+//!
+//! Each builder takes the program's own argument struct from [`args`], so the
+//! encoded data always matches the deployed interface. User token accounts are
+//! the associated token accounts of the signer, except in the `_to` builders,
+//! which take them explicitly.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{system_program, InstructionData};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token::{self, spl_token::native_mint};
+use amm::state::Pool;
+use crate::pda::{
+    find_config_address, find_farm_address, find_farm_lp_vault_address,
+    find_farm_reward_vault_address, find_position_address, find_temp_wsol_address,
+    find_user_stake_address,
+};
+
+/// Instruction argument structs generated from the program
+pub use amm::instruction as args;
+
+/// Addresses of a pool and the accounts it references
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolKeys {
+    pub pool: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    pub lp_mint: Pubkey,
+}
+
+impl PoolKeys {
+    /// Collect the keys of a deserialized pool stored at `pool`
+    pub fn new(pool: Pubkey, state: &Pool) -> Self {
+        Self {
+            pool,
+            token_mint_a: state.token_mint_a,
+            token_mint_b: state.token_mint_b,
+            vault_a: state.vault_a,
+            vault_b: state.vault_b,
+            lp_mint: state.lp_mint,
+        }
+    }
+    
+    /// `(vault_input, vault_output, output_mint)` for a swap paying `input_mint`
+    pub fn swap_accounts(&self, input_mint: &Pubkey) -> (Pubkey, Pubkey, Pubkey) {
+        if *input_mint == self.token_mint_a {
+            (self.vault_a, self.vault_b, self.token_mint_b)
+        } else {
+            (self.vault_b, self.vault_a, self.token_mint_a)
+        }
+    }
+    
+    /// The side of a native SOL pool that is not the native mint
+    pub fn non_native_mint(&self) -> Pubkey {
+        if self.token_mint_a == native_mint::ID {
+            self.token_mint_b
+        } else {
+            self.token_mint_a
+        }
+    }
+}
+
+fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: amm::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize_config(admin: &Pubkey) -> Instruction {
+    instruction(
+        amm::accounts::InitializeConfig {
+            admin: *admin,
+            config: find_config_address().0,
+            system_program: system_program::ID,
+        },
+        args::InitializeConfig {},
+    )
+}
+
+/// Register a pool whose vaults and LP mint were already created with the pool
+/// PDA as authority
+pub fn initialize_pool(payer: &Pubkey, keys: &PoolKeys) -> Instruction {
+    instruction(
+        amm::accounts::InitializePool {
+            payer: *payer,
+            config: find_config_address().0,
+            pool: keys.pool,
+            token_mint_a: keys.token_mint_a,
+            token_mint_b: keys.token_mint_b,
+            vault_a: keys.vault_a,
+            vault_b: keys.vault_b,
+            lp_mint: keys.lp_mint,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        args::InitializePool {},
+    )
+}
+
+/// Close a pool with no LP supply, sweeping what is left in its vaults to
+/// `destination_a` and `destination_b` and its rent to `recipient`
+pub fn close_pool(
+    admin: &Pubkey,
+    keys: &PoolKeys,
+    destination_a: &Pubkey,
+    destination_b: &Pubkey,
+    recipient: &Pubkey,
+) -> Instruction {
+    instruction(
+        amm::accounts::ClosePool {
+            admin: *admin,
+            config: find_config_address().0,
+            pool: keys.pool,
+            vault_a: keys.vault_a,
+            vault_b: keys.vault_b,
+            lp_mint: keys.lp_mint,
+            destination_a: *destination_a,
+            destination_b: *destination_b,
+            recipient: *recipient,
+            token_program: token::ID,
+        },
+        args::ClosePool {},
+    )
+}
+
+/// Deposit and mint LP tokens to the user's associated LP token account,
+/// which the program creates if it does not exist
+pub fn add_liquidity(user: &Pubkey, keys: &PoolKeys, args: args::AddLiquidity) -> Instruction {
+    add_liquidity_into(user, keys, None, args)
+}
+
+/// Deposit and mint LP tokens to `user_lp`, an existing LP token account of the user
+pub fn add_liquidity_to(user: &Pubkey, keys: &PoolKeys, user_lp: &Pubkey, args: args::AddLiquidity) -> Instruction {
+    add_liquidity_into(user, keys, Some(*user_lp), args)
+}
+
+fn add_liquidity_into(
+    user: &Pubkey,
+    keys: &PoolKeys,
+    user_lp: Option<Pubkey>,
+    args: args::AddLiquidity,
+) -> Instruction {
+    instruction(
+        amm::accounts::AddLiquidity {
+            user: *user,
+            pool: keys.pool,
+            token_mint_a: keys.token_mint_a,
+            token_mint_b: keys.token_mint_b,
+            vault_a: keys.vault_a,
+            vault_b: keys.vault_b,
+            lp_mint: keys.lp_mint,
+            user_token_a: get_associated_token_address(user, &keys.token_mint_a),
+            user_token_b: get_associated_token_address(user, &keys.token_mint_b),
+            user_lp,
+            user_lp_ata: user_lp
+                .is_none()
+                .then(|| get_associated_token_address(user, &keys.lp_mint)),
+            position: find_position_address(&keys.pool, user).0,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn add_liquidity_single(user: &Pubkey, keys: &PoolKeys, args: args::AddLiquiditySingle) -> Instruction {
+    instruction(
+        amm::accounts::AddLiquiditySingle {
+            user: *user,
+            pool: keys.pool,
+            vault_a: keys.vault_a,
+            vault_b: keys.vault_b,
+            lp_mint: keys.lp_mint,
+            user_token_in: get_associated_token_address(user, &args.token_in),
+            user_lp: get_associated_token_address(user, &keys.lp_mint),
+            position: find_position_address(&keys.pool, user).0,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn add_liquidity_exact_lp(user: &Pubkey, keys: &PoolKeys, args: args::AddLiquidityExactLp) -> Instruction {
+    instruction(
+        amm::accounts::AddLiquidityExactLp {
+            user: *user,
+            pool: keys.pool,
+            vault_a: keys.vault_a,
+            vault_b: keys.vault_b,
+            lp_mint: keys.lp_mint,
+            user_token_a: get_associated_token_address(user, &keys.token_mint_a),
+            user_token_b: get_associated_token_address(user, &keys.token_mint_b),
+            user_lp: get_associated_token_address(user, &keys.lp_mint),
+            position: find_position_address(&keys.pool, user).0,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn add_liquidity_native(user: &Pubkey, keys: &PoolKeys, args: args::AddLiquidityNative) -> Instruction {
+    instruction(
+        amm::accounts::AddLiquidityNative {
+            user: *user,
+            pool: keys.pool,
+            vault_a: keys.vault_a,
+            vault_b: keys.vault_b,
+            lp_mint: keys.lp_mint,
+            user_token: get_associated_token_address(user, &keys.non_native_mint()),
+            user_lp: get_associated_token_address(user, &keys.lp_mint),
+            position: find_position_address(&keys.pool, user).0,
+            temp_wsol: find_temp_wsol_address(user).0,
+            native_mint: native_mint::ID,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+/// Withdraw into the user's associated token accounts, which the program
+/// creates if they do not exist
+pub fn remove_liquidity(user: &Pubkey, keys: &PoolKeys, args: args::RemoveLiquidity) -> Instruction {
+    remove_liquidity_into(user, keys, None, args)
+}
+
+/// Withdraw into `user_token_a` and `user_token_b`, existing token accounts of the user
+pub fn remove_liquidity_to(
+    user: &Pubkey,
+    keys: &PoolKeys,
+    user_token_a: &Pubkey,
+    user_token_b: &Pubkey,
+    args: args::RemoveLiquidity,
+) -> Instruction {
+    remove_liquidity_into(user, keys, Some((*user_token_a, *user_token_b)), args)
+}
+
+fn remove_liquidity_into(
+    user: &Pubkey,
+    keys: &PoolKeys,
+    user_tokens: Option<(Pubkey, Pubkey)>,
+    args: args::RemoveLiquidity,
+) -> Instruction {
+    instruction(
+        amm::accounts::RemoveLiquidity {
+            user: *user,
+            pool: keys.pool,
+            token_mint_a: keys.token_mint_a,
+            token_mint_b: keys.token_mint_b,
+            vault_a: keys.vault_a,
+            vault_b: keys.vault_b,
+            lp_mint: keys.lp_mint,
+            user_lp: get_associated_token_address(user, &keys.lp_mint),
+            user_token_a: user_tokens.map(|(token_a, _)| token_a),
+            user_token_b: user_tokens.map(|(_, token_b)| token_b),
+            user_token_a_ata: user_tokens
+                .is_none()
+                .then(|| get_associated_token_address(user, &keys.token_mint_a)),
+            user_token_b_ata: user_tokens
+                .is_none()
+                .then(|| get_associated_token_address(user, &keys.token_mint_b)),
+            position: find_position_address(&keys.pool, user).0,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+pub fn remove_liquidity_single(user: &Pubkey, keys: &PoolKeys, args: args::RemoveLiquiditySingle) -> Instruction {
+    instruction(
+        amm::accounts::RemoveLiquiditySingle {
+            user: *user,
+            pool: keys.pool,
+            vault_a: keys.vault_a,
+            vault_b: keys.vault_b,
+            lp_mint: keys.lp_mint,
+            user_lp: get_associated_token_address(user, &keys.lp_mint),
+            position: find_position_address(&keys.pool, user).0,
+            user_token_out: get_associated_token_address(user, &args.token_out),
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+pub fn remove_liquidity_exact_out(user: &Pubkey, keys: &PoolKeys, args: args::RemoveLiquidityExactOut) -> Instruction {
+    instruction(
+        amm::accounts::RemoveLiquidityExactOut {
+            user: *user,
+            pool: keys.pool,
+            token_mint_a: keys.token_mint_a,
+            token_mint_b: keys.token_mint_b,
+            vault_a: keys.vault_a,
+            vault_b: keys.vault_b,
+            lp_mint: keys.lp_mint,
+            user_lp: get_associated_token_address(user, &keys.lp_mint),
+            position: find_position_address(&keys.pool, user).0,
+            user_token_a: get_associated_token_address(user, &keys.token_mint_a),
+            user_token_b: get_associated_token_address(user, &keys.token_mint_b),
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+pub fn remove_liquidity_native(user: &Pubkey, keys: &PoolKeys, args: args::RemoveLiquidityNative) -> Instruction {
+    instruction(
+        amm::accounts::RemoveLiquidityNative {
+            user: *user,
+            pool: keys.pool,
+            vault_a: keys.vault_a,
+            vault_b: keys.vault_b,
+            lp_mint: keys.lp_mint,
+            user_lp: get_associated_token_address(user, &keys.lp_mint),
+            position: find_position_address(&keys.pool, user).0,
+            user_token: get_associated_token_address(user, &keys.non_native_mint()),
+            temp_wsol: find_temp_wsol_address(user).0,
+            native_mint: native_mint::ID,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+/// View instruction; read the `PositionStats` from a simulation's return data
+pub fn get_position_stats(keys: &PoolKeys, owner: &Pubkey) -> Instruction {
+    instruction(
+        amm::accounts::GetPositionStats {
+            pool: keys.pool,
+            vault_a: keys.vault_a,
+            vault_b: keys.vault_b,
+            lp_mint: keys.lp_mint,
+            position: find_position_address(&keys.pool, owner).0,
+        },
+        args::GetPositionStats {},
+    )
+}
+
+/// Swap paying `input_mint` into the user's associated token account for the
+/// other side, which the program creates if it does not exist. `referrer_token`
+/// receives the referral cut, if any.
+pub fn swap(
+    user: &Pubkey,
+    keys: &PoolKeys,
+    input_mint: &Pubkey,
+    referrer_token: Option<Pubkey>,
+    args: args::Swap,
+) -> Instruction {
+    swap_into(user, keys, input_mint, None, referrer_token, args)
+}
+
+/// Swap paying `input_mint` into `user_output`, an existing token account of the user
+pub fn swap_to(
+    user: &Pubkey,
+    keys: &PoolKeys,
+    input_mint: &Pubkey,
+    user_output: &Pubkey,
+    referrer_token: Option<Pubkey>,
+    args: args::Swap,
+) -> Instruction {
+    swap_into(user, keys, input_mint, Some(*user_output), referrer_token, args)
+}
+
+fn swap_into(
+    user: &Pubkey,
+    keys: &PoolKeys,
+    input_mint: &Pubkey,
+    user_output: Option<Pubkey>,
+    referrer_token: Option<Pubkey>,
+    args: args::Swap,
+) -> Instruction {
+    let (vault_input, vault_output, output_mint) = keys.swap_accounts(input_mint);
+    instruction(
+        amm::accounts::Swap {
+            user: *user,
+            pool: keys.pool,
+            config: find_config_address().0,
+            token_mint_a: keys.token_mint_a,
+            token_mint_b: keys.token_mint_b,
+            output_mint,
+            user_input: get_associated_token_address(user, input_mint),
+            user_output,
+            vault_input,
+            vault_output,
+            referrer_token,
+            user_output_ata: user_output
+                .is_none()
+                .then(|| get_associated_token_address(user, &output_mint)),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        args,
+    )
+}
+
+/// Swap against a native SOL pool paying `input_mint`, which may be the native mint
+pub fn swap_native(user: &Pubkey, keys: &PoolKeys, input_mint: &Pubkey, args: args::SwapNative) -> Instruction {
+    let (vault_input, vault_output, _) = keys.swap_accounts(input_mint);
+    instruction(
+        amm::accounts::SwapNative {
+            user: *user,
+            pool: keys.pool,
+            vault_input,
+            vault_output,
+            user_token: get_associated_token_address(user, &keys.non_native_mint()),
+            temp_wsol: find_temp_wsol_address(user).0,
+            native_mint: native_mint::ID,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+pub fn set_max_referral_bps(admin: &Pubkey, max_referral_bps: u16) -> Instruction {
+    instruction(
+        amm::accounts::SetMaxReferralBps {
+            admin: *admin,
+            config: find_config_address().0,
+        },
+        args::SetMaxReferralBps { max_referral_bps },
+    )
+}
+
+pub fn set_emergency_mode(admin: &Pubkey, pool: &Pubkey, enabled: bool) -> Instruction {
+    instruction(
+        amm::accounts::SetEmergencyMode {
+            admin: *admin,
+            config: find_config_address().0,
+            pool: *pool,
+        },
+        args::SetEmergencyMode { enabled },
+    )
+}
+
+pub fn migrate_config(admin: &Pubkey) -> Instruction {
+    instruction(
+        amm::accounts::MigrateConfig {
+            admin: *admin,
+            config: find_config_address().0,
+            system_program: system_program::ID,
+        },
+        args::MigrateConfig {},
+    )
+}
+
+pub fn migrate_pool(admin: &Pubkey, pool: &Pubkey) -> Instruction {
+    instruction(
+        amm::accounts::MigratePool {
+            admin: *admin,
+            config: find_config_address().0,
+            pool: *pool,
+            system_program: system_program::ID,
+        },
+        args::MigratePool {},
+    )
+}
+
+pub fn recover_tokens(
+    admin: &Pubkey,
+    pool: &Pubkey,
+    source: &Pubkey,
+    destination: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    instruction(
+        amm::accounts::RecoverTokens {
+            admin: *admin,
+            config: find_config_address().0,
+            pool: *pool,
+            source: *source,
+            destination: *destination,
+            token_program: token::ID,
+        },
+        args::RecoverTokens { amount },
+    )
+}
+
+pub fn initialize_farm(admin: &Pubkey, keys: &PoolKeys) -> Instruction {
+    let farm = find_farm_address(&keys.pool).0;
+    instruction(
+        amm::accounts::InitializeFarm {
+            admin: *admin,
+            config: find_config_address().0,
+            pool: keys.pool,
+            farm,
+            lp_mint: keys.lp_mint,
+            lp_vault: find_farm_lp_vault_address(&farm).0,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        args::InitializeFarm {},
+    )
+}
+
+pub fn add_reward_stream(admin: &Pubkey, farm: &Pubkey, reward_mint: &Pubkey, funder: Pubkey) -> Instruction {
+    instruction(
+        amm::accounts::AddRewardStream {
+            admin: *admin,
+            config: find_config_address().0,
+            farm: *farm,
+            reward_mint: *reward_mint,
+            reward_vault: find_farm_reward_vault_address(farm, reward_mint).0,
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        args::AddRewardStream { funder },
+    )
+}
+
+pub fn fund_reward_stream(
+    funder: &Pubkey,
+    farm: &Pubkey,
+    reward_mint: &Pubkey,
+    args: args::FundRewardStream,
+) -> Instruction {
+    instruction(
+        amm::accounts::FundRewardStream {
+            funder: *funder,
+            farm: *farm,
+            reward_vault: find_farm_reward_vault_address(farm, reward_mint).0,
+            funder_reward: get_associated_token_address(funder, reward_mint),
+            token_program: token::ID,
+        },
+        args,
+    )
+}
+
+pub fn stake_lp(user: &Pubkey, keys: &PoolKeys, amount: u64) -> Instruction {
+    let farm = find_farm_address(&keys.pool).0;
+    instruction(
+        amm::accounts::StakeLp {
+            user: *user,
+            farm,
+            user_stake: find_user_stake_address(&farm, user).0,
+            lp_vault: find_farm_lp_vault_address(&farm).0,
+            user_lp: get_associated_token_address(user, &keys.lp_mint),
+            system_program: system_program::ID,
+            token_program: token::ID,
+        },
+        args::StakeLp { amount },
+    )
+}
+
+pub fn unstake_lp(user: &Pubkey, keys: &PoolKeys, amount: u64) -> Instruction {
+    let farm = find_farm_address(&keys.pool).0;
+    instruction(
+        amm::accounts::UnstakeLp {
+            user: *user,
+            farm,
+            user_stake: find_user_stake_address(&farm, user).0,
+            lp_vault: find_farm_lp_vault_address(&farm).0,
+            user_lp: get_associated_token_address(user, &keys.lp_mint),
+            token_program: token::ID,
+        },
+        args::UnstakeLp { amount },
+    )
+}
+
+/// Withdraw the whole stake, forfeiting unclaimed rewards
+pub fn emergency_unstake_lp(user: &Pubkey, keys: &PoolKeys) -> Instruction {
+    let farm = find_farm_address(&keys.pool).0;
+    instruction(
+        amm::accounts::UnstakeLp {
+            user: *user,
+            farm,
+            user_stake: find_user_stake_address(&farm, user).0,
+            lp_vault: find_farm_lp_vault_address(&farm).0,
+            user_lp: get_associated_token_address(user, &keys.lp_mint),
+            token_program: token::ID,
+        },
+        args::EmergencyUnstakeLp {},
+    )
+}
+
+pub fn claim_rewards(user: &Pubkey, farm: &Pubkey, reward_mint: &Pubkey, stream_index: u8) -> Instruction {
+    instruction(
+        amm::accounts::ClaimRewards {
+            user: *user,
+            farm: *farm,
+            user_stake: find_user_stake_address(farm, user).0,
+            reward_mint: *reward_mint,
+            reward_vault: find_farm_reward_vault_address(farm, reward_mint).0,
+            user_reward: get_associated_token_address(user, reward_mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        args::ClaimRewards { stream_index },
+    )
+}
+
+/// Claim every stream in `reward_mints`, which must list the farm's active
+/// streams in slot order. The user's reward token accounts must already exist.
+pub fn claim_all_rewards(user: &Pubkey, farm: &Pubkey, reward_mints: &[Pubkey]) -> Instruction {
+    let mut ix = instruction(
+        amm::accounts::ClaimAllRewards {
+            user: *user,
+            farm: *farm,
+            user_stake: find_user_stake_address(farm, user).0,
+            token_program: token::ID,
+        },
+        args::ClaimAllRewards {},
+    );
+    for reward_mint in reward_mints {
+        ix.accounts.push(AccountMeta::new(find_farm_reward_vault_address(farm, reward_mint).0, false));
+        ix.accounts.push(AccountMeta::new(get_associated_token_address(user, reward_mint), false));
+    }
+    ix
+}
@@ -0,0 +1,15 @@
+//! Rust client for the AMM program.
+//!
+//! - [`pda`]: program address derivation
+//! - [`instructions`]: typed builders for every program instruction
+//! - [`accounts`]: deserializers for program accounts
+//! - [`quote`]: off-chain quotes using the program's own math
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+pub mod quote;
+
+pub use amm::ID as PROGRAM_ID;
+pub use amm::state::{Config, Farm, LpPosition, Pool, RewardStream, UserStake};
+pub use instructions::PoolKeys;
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::Pubkey;
+use amm::native_sol::WSOL_SEED;
+
+/// Order two mints the way pools are keyed (`token_mint_a < token_mint_b`)
+pub fn sort_mints(mint_x: Pubkey, mint_y: Pubkey) -> (Pubkey, Pubkey) {
+    if mint_x < mint_y {
+        (mint_x, mint_y)
+    } else {
+        (mint_y, mint_x)
+    }
+}
+
+/// Global config PDA
+pub fn find_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"config"], &amm::ID)
+}
+
+/// Pool PDA for a mint pair; mints must already be in pool order
+pub fn find_pool_address(token_mint_a: &Pubkey, token_mint_b: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"pool", token_mint_a.as_ref(), token_mint_b.as_ref()], &amm::ID)
+}
+
+/// Per-user LP position PDA
+pub fn find_position_address(pool: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"position", pool.as_ref(), user.as_ref()], &amm::ID)
+}
+
+/// Farm PDA for a pool
+pub fn find_farm_address(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"farm", pool.as_ref()], &amm::ID)
+}
+
+/// Vault holding a farm's staked LP tokens
+pub fn find_farm_lp_vault_address(farm: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"farm_lp", farm.as_ref()], &amm::ID)
+}
+
+/// Vault holding a farm's undistributed rewards for one reward mint
+pub fn find_farm_reward_vault_address(farm: &Pubkey, reward_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"farm_reward", farm.as_ref(), reward_mint.as_ref()], &amm::ID)
+}
+
+/// Per-user stake PDA in a farm
+pub fn find_user_stake_address(farm: &Pubkey, user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"stake", farm.as_ref(), user.as_ref()], &amm::ID)
+}
+
+/// Temporary wSOL account used by the native SOL instructions
+pub fn find_temp_wsol_address(user: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WSOL_SEED, user.as_ref()], &amm::ID)
+}
@@ -0,0 +1,189 @@
+//! Off-chain quotes. Each function mirrors the checks and rounding of the
+//! matching instruction using `amm::math`, so a quote that succeeds here
+//! succeeds on-chain against the same reserves.
+
+use anchor_lang::prelude::*;
+use amm::error::AmmError;
+use amm::math::{
+    get_amount_out, get_deposit_amounts, get_exact_lp_deposit_amounts, get_lp_for_exact_withdrawal,
+    get_single_sided_deposit_lp, get_single_sided_withdraw_amount, get_withdraw_amounts, quote,
+};
+use amm::state::Pool;
+
+/// Pool balances as read from its vaults and LP mint
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PoolReserves {
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+    pub lp_supply: u64,
+}
+
+impl PoolReserves {
+    /// `(reserve_in, reserve_out)` when paying `mint`
+    fn oriented(&self, pool: &Pool, mint: &Pubkey) -> Result<(u64, u64)> {
+        if *mint == pool.token_mint_a {
+            Ok((self.reserve_a, self.reserve_b))
+        } else if *mint == pool.token_mint_b {
+            Ok((self.reserve_b, self.reserve_a))
+        } else {
+            Err(AmmError::InvalidMint.into())
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwapQuote {
+    /// Tokens received
+    pub amount_out: u64,
+    /// Part of the input kept by the pool as LP fee
+    pub fee_amount: u64,
+    /// Part of the input paid to the referrer
+    pub referral_amount: u64,
+    /// Shortfall against the spot price after fees, in basis points
+    pub price_impact_bps: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DepositQuote {
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub lp_tokens: u64,
+}
+
+/// Quote `swap` paying `amount_in` of `input_mint`. Pass `referral_bps = 0`
+/// when no referrer account will be supplied.
+pub fn quote_swap(
+    pool: &Pool,
+    reserves: &PoolReserves,
+    input_mint: &Pubkey,
+    amount_in: u64,
+    referral_bps: u16,
+) -> Result<SwapQuote> {
+    require!(!pool.is_emergency_mode(), AmmError::EmergencyMode);
+    require!(amount_in > 0, AmmError::ZeroLiquidity);
+    
+    let (reserve_in, reserve_out) = reserves.oriented(pool, input_mint)?;
+    require!(reserve_in > 0 && reserve_out > 0, AmmError::PoolNotReady);
+    
+    let referral_amount = u64::try_from((amount_in as u128) * (referral_bps as u128) / 10_000)
+        .map_err(|_| AmmError::MathOverflow)?;
+    let amount_in_after_referral = amount_in
+        .checked_sub(referral_amount)
+        .ok_or(AmmError::MathOverflow)?;
+    
+    let amount_out = get_amount_out(amount_in_after_referral, reserve_in, reserve_out, pool.fee_bps)?;
+    require!(amount_out < reserve_out, AmmError::InsufficientLiquidity);
+    
+    let fee_amount = u64::try_from((amount_in_after_referral as u128) * (pool.fee_bps as u128) / 10_000)
+        .map_err(|_| AmmError::MathOverflow)?;
+    let spot_out = quote(amount_in_after_referral - fee_amount, reserve_in, reserve_out)?;
+    let price_impact_bps = if spot_out > amount_out {
+        (((spot_out - amount_out) as u128) * 10_000 / spot_out as u128) as u64
+    } else {
+        0
+    };
+    
+    Ok(SwapQuote {
+        amount_out,
+        fee_amount,
+        referral_amount,
+        price_impact_bps,
+    })
+}
+
+/// Quote `add_liquidity`; the deposit is trimmed to the reserve ratio
+pub fn quote_add_liquidity(
+    pool: &Pool,
+    reserves: &PoolReserves,
+    amount_a_max: u64,
+    amount_b_max: u64,
+) -> Result<DepositQuote> {
+    require!(!pool.is_emergency_mode(), AmmError::EmergencyMode);
+    require!(amount_a_max > 0 && amount_b_max > 0, AmmError::ZeroLiquidity);
+    
+    let (amount_a, amount_b, lp_tokens) = get_deposit_amounts(
+        amount_a_max,
+        amount_b_max,
+        0,
+        0,
+        reserves.reserve_a,
+        reserves.reserve_b,
+        reserves.lp_supply,
+    )?;
+    
+    Ok(DepositQuote { amount_a, amount_b, lp_tokens })
+}
+
+/// Quote `add_liquidity_single`, returning the LP tokens minted
+pub fn quote_add_liquidity_single(
+    pool: &Pool,
+    reserves: &PoolReserves,
+    token_in: &Pubkey,
+    amount_in: u64,
+) -> Result<u64> {
+    require!(!pool.is_emergency_mode(), AmmError::EmergencyMode);
+    require!(amount_in > 0, AmmError::ZeroLiquidity);
+    
+    let (reserve_in, reserve_out) = reserves.oriented(pool, token_in)?;
+    let lp_supply = reserves.lp_supply;
+    require!(reserve_in > 0 && reserve_out > 0 && lp_supply > 0, AmmError::PoolNotReady);
+    
+    get_single_sided_deposit_lp(amount_in, reserve_in, reserve_out, lp_supply, pool.fee_bps)
+}
+
+/// Quote `add_liquidity_exact_lp`, returning the `(amount_a, amount_b)` charged
+pub fn quote_add_liquidity_exact_lp(
+    pool: &Pool,
+    reserves: &PoolReserves,
+    lp_out: u64,
+) -> Result<(u64, u64)> {
+    require!(!pool.is_emergency_mode(), AmmError::EmergencyMode);
+    require!(lp_out > 0, AmmError::ZeroLiquidity);
+    require!(
+        reserves.reserve_a > 0 && reserves.reserve_b > 0 && reserves.lp_supply > 0,
+        AmmError::PoolNotReady
+    );
+    
+    get_exact_lp_deposit_amounts(lp_out, reserves.reserve_a, reserves.reserve_b, reserves.lp_supply)
+}
+
+/// Quote `remove_liquidity`, returning the `(amount_a, amount_b)` withdrawn
+pub fn quote_remove_liquidity(reserves: &PoolReserves, lp_amount: u64) -> Result<(u64, u64)> {
+    require!(lp_amount > 0, AmmError::ZeroLiquidity);
+    
+    get_withdraw_amounts(lp_amount, reserves.reserve_a, reserves.reserve_b, reserves.lp_supply)
+}
+
+/// Quote `remove_liquidity_single`, returning the amount of `token_out` received
+pub fn quote_remove_liquidity_single(
+    pool: &Pool,
+    reserves: &PoolReserves,
+    token_out: &Pubkey,
+    lp_amount: u64,
+) -> Result<u64> {
+    require!(!pool.is_emergency_mode(), AmmError::EmergencyMode);
+    require!(lp_amount > 0, AmmError::ZeroLiquidity);
+    
+    let (reserve_out, reserve_other) = reserves.oriented(pool, token_out)?;
+    get_single_sided_withdraw_amount(lp_amount, reserve_out, reserve_other, reserves.lp_supply, pool.fee_bps)
+}
+
+/// Quote `remove_liquidity_exact_out`, returning the LP tokens burned
+pub fn quote_remove_liquidity_exact_out(
+    pool: &Pool,
+    reserves: &PoolReserves,
+    amount_a: u64,
+    amount_b: u64,
+) -> Result<u64> {
+    require!(!pool.is_emergency_mode(), AmmError::EmergencyMode);
+    require!(amount_a > 0 || amount_b > 0, AmmError::ZeroLiquidity);
+    
+    get_lp_for_exact_withdrawal(
+        amount_a,
+        amount_b,
+        reserves.reserve_a,
+        reserves.reserve_b,
+        reserves.lp_supply,
+        pool.fee_bps,
+    )
+}
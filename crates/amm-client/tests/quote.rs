@@ -0,0 +1,252 @@
+//! Quotes against the program math they mirror, including the rounding the
+//! instructions apply.
+
+use amm::error::AmmError;
+use amm::math::{get_amount_out, get_deposit_amounts, get_withdraw_amounts, integer_sqrt};
+use amm::state::Pool;
+use amm_client::quote::{
+    quote_add_liquidity, quote_remove_liquidity, quote_swap, DepositQuote, PoolReserves, SwapQuote,
+};
+use anchor_lang::prelude::Pubkey;
+
+const RESERVES: PoolReserves = PoolReserves {
+    reserve_a: 1_000_000_000,
+    reserve_b: 4_000_000_000,
+    lp_supply: 2_000_000_000,
+};
+
+fn pool() -> Pool {
+    let mut pool: Pool = bytemuck::Zeroable::zeroed();
+    pool.token_mint_a = Pubkey::new_unique();
+    pool.token_mint_b = Pubkey::new_unique();
+    pool.fee_bps = 30;
+    pool
+}
+
+#[test]
+fn swap_pays_the_program_amount_out() {
+    let pool = pool();
+
+    let quote = quote_swap(&pool, &RESERVES, &pool.token_mint_a, 10_000_000, 0).unwrap();
+    assert_eq!(
+        quote.amount_out,
+        get_amount_out(10_000_000, 1_000_000_000, 4_000_000_000, 30).unwrap()
+    );
+    assert_eq!(quote.fee_amount, 30_000);
+    assert_eq!(quote.referral_amount, 0);
+
+    let quote = quote_swap(&pool, &RESERVES, &pool.token_mint_b, 10_000_000, 0).unwrap();
+    assert_eq!(
+        quote.amount_out,
+        get_amount_out(10_000_000, 4_000_000_000, 1_000_000_000, 30).unwrap()
+    );
+}
+
+#[test]
+fn swap_fee_and_referral_round_down() {
+    let pool = pool();
+
+    // 333 * 30 / 10_000 is below one token, so no fee is reported; the output
+    // still prices the fee exactly, as the program does
+    let quote = quote_swap(&pool, &RESERVES, &pool.token_mint_a, 333, 0).unwrap();
+    assert_eq!(quote.fee_amount, 0);
+    assert_eq!(
+        quote.amount_out,
+        get_amount_out(333, 1_000_000_000, 4_000_000_000, 30).unwrap()
+    );
+    assert!(quote.amount_out < 333 * 4);
+
+    let quote = quote_swap(&pool, &RESERVES, &pool.token_mint_a, 10_001, 0).unwrap();
+    assert_eq!(quote.fee_amount, 30);
+
+    // The referral cut comes off the input before the pool prices it
+    let quote = quote_swap(&pool, &RESERVES, &pool.token_mint_a, 10_000_199, 50).unwrap();
+    assert_eq!(quote.referral_amount, 50_000);
+    assert_eq!(quote.fee_amount, 29_850);
+    assert_eq!(
+        quote.amount_out,
+        get_amount_out(9_950_199, 1_000_000_000, 4_000_000_000, 30).unwrap()
+    );
+}
+
+#[test]
+fn swap_price_impact() {
+    let pool = pool();
+
+    // A tenth of the input reserve moves the price by about 1 / 11
+    let quote = quote_swap(&pool, &RESERVES, &pool.token_mint_a, 100_000_000, 0).unwrap();
+    assert_eq!(
+        quote,
+        SwapQuote {
+            amount_out: 362_644_357,
+            fee_amount: 300_000,
+            referral_amount: 0,
+            price_impact_bps: 906,
+        }
+    );
+
+    // A thousandth of it by about 1 / 1001, rounded down
+    let quote = quote_swap(&pool, &RESERVES, &pool.token_mint_a, 1_000_000, 0).unwrap();
+    assert_eq!(quote.price_impact_bps, 9);
+}
+
+#[test]
+fn swap_errors() {
+    let mut pool = pool();
+
+    let error = quote_swap(&pool, &RESERVES, &pool.token_mint_a, 0, 0).unwrap_err();
+    assert_eq!(error, AmmError::ZeroLiquidity.into());
+
+    let error = quote_swap(&pool, &RESERVES, &Pubkey::new_unique(), 1_000, 0).unwrap_err();
+    assert_eq!(error, AmmError::InvalidMint.into());
+
+    let empty = PoolReserves::default();
+    let error = quote_swap(&pool, &empty, &pool.token_mint_a, 1_000, 0).unwrap_err();
+    assert_eq!(error, AmmError::PoolNotReady.into());
+
+    pool.emergency_mode = 1;
+    let error = quote_swap(&pool, &RESERVES, &pool.token_mint_a, 1_000, 0).unwrap_err();
+    assert_eq!(error, AmmError::EmergencyMode.into());
+}
+
+#[test]
+fn deposit_is_trimmed_to_the_reserve_ratio() {
+    let pool = pool();
+
+    // Too much B: all of A is taken with the matching B
+    let quote = quote_add_liquidity(&pool, &RESERVES, 10_000_000, 100_000_000).unwrap();
+    assert_eq!(
+        quote,
+        DepositQuote {
+            amount_a: 10_000_000,
+            amount_b: 40_000_000,
+            lp_tokens: 20_000_000,
+        }
+    );
+
+    // Too much A: all of B is taken with the matching A
+    let quote = quote_add_liquidity(&pool, &RESERVES, 100_000_000, 4_000_000).unwrap();
+    assert_eq!(
+        quote,
+        DepositQuote {
+            amount_a: 1_000_000,
+            amount_b: 4_000_000,
+            lp_tokens: 2_000_000,
+        }
+    );
+}
+
+#[test]
+fn trimmed_deposit_rounds_in_favour_of_the_pool() {
+    let pool = pool();
+    let reserves = PoolReserves {
+        reserve_a: 1_000_000_007,
+        reserve_b: 3_000_000_011,
+        lp_supply: 1_732_050_808,
+    };
+
+    let quote = quote_add_liquidity(&pool, &reserves, 1_000, 1_000_000).unwrap();
+    let (amount_a, amount_b, lp_tokens) = get_deposit_amounts(
+        1_000,
+        1_000_000,
+        0,
+        0,
+        reserves.reserve_a,
+        reserves.reserve_b,
+        reserves.lp_supply,
+    )
+    .unwrap();
+    assert_eq!(
+        (quote.amount_a, quote.amount_b, quote.lp_tokens),
+        (amount_a, amount_b, lp_tokens)
+    );
+
+    // B is rounded down, and the LP minted is the smaller of the two shares:
+    // 1_732 for the A side but only 1_731 for the trimmed B
+    assert_eq!(quote.amount_b, 2_999);
+    assert_eq!(quote.lp_tokens, 1_731);
+    assert!(
+        quote.lp_tokens as u128 * reserves.reserve_a as u128
+            <= quote.amount_a as u128 * reserves.lp_supply as u128
+    );
+    assert!(
+        quote.lp_tokens as u128 * reserves.reserve_b as u128
+            <= quote.amount_b as u128 * reserves.lp_supply as u128
+    );
+}
+
+#[test]
+fn first_deposit_mints_the_geometric_mean() {
+    let pool = pool();
+
+    let quote = quote_add_liquidity(
+        &pool,
+        &PoolReserves::default(),
+        1_000_000_000,
+        4_000_000_000,
+    )
+    .unwrap();
+    assert_eq!(quote.amount_a, 1_000_000_000);
+    assert_eq!(quote.amount_b, 4_000_000_000);
+    assert_eq!(
+        quote.lp_tokens as u128,
+        integer_sqrt(4_000_000_000_000_000_000)
+    );
+}
+
+#[test]
+fn deposit_errors() {
+    let mut pool = pool();
+
+    let error = quote_add_liquidity(&pool, &RESERVES, 0, 1_000).unwrap_err();
+    assert_eq!(error, AmmError::ZeroLiquidity.into());
+
+    // Too small to buy any B at this ratio
+    let error = quote_add_liquidity(&pool, &RESERVES, 1_000, 3).unwrap_err();
+    assert_eq!(error, AmmError::ZeroLiquidity.into());
+
+    pool.emergency_mode = 1;
+    let error = quote_add_liquidity(&pool, &RESERVES, 1_000, 4_000).unwrap_err();
+    assert_eq!(error, AmmError::EmergencyMode.into());
+}
+
+#[test]
+fn withdrawal_is_pro_rata_rounded_down() {
+    let reserves = PoolReserves {
+        reserve_a: 1_000_000_007,
+        reserve_b: 3_000_000_011,
+        lp_supply: 1_732_050_808,
+    };
+
+    let (amount_a, amount_b) = quote_remove_liquidity(&reserves, 1_000_000).unwrap();
+    assert_eq!(
+        (amount_a, amount_b),
+        get_withdraw_amounts(
+            1_000_000,
+            reserves.reserve_a,
+            reserves.reserve_b,
+            reserves.lp_supply
+        )
+        .unwrap()
+    );
+    assert_eq!((amount_a, amount_b), (577_350, 1_732_050));
+
+    // Burning the whole supply pays out the whole reserves
+    assert_eq!(
+        quote_remove_liquidity(&reserves, reserves.lp_supply).unwrap(),
+        (reserves.reserve_a, reserves.reserve_b)
+    );
+}
+
+#[test]
+fn withdrawal_errors() {
+    let error = quote_remove_liquidity(&RESERVES, 0).unwrap_err();
+    assert_eq!(error, AmmError::ZeroLiquidity.into());
+
+    let error = quote_remove_liquidity(&RESERVES, RESERVES.lp_supply + 1).unwrap_err();
+    assert_eq!(error, AmmError::InsufficientLiquidity.into());
+
+    // Too little LP to withdraw a whole token of A
+    let error = quote_remove_liquidity(&RESERVES, 1).unwrap_err();
+    assert_eq!(error, AmmError::InsufficientLiquidity.into());
+}
@@ -0,0 +1,175 @@
+//! Jupiter [`Amm`] adapter for the AMM program's pools.
+//!
+//! The router loads a pool with [`Amm::from_keyed_account`], keeps it current by
+//! fetching [`Amm::get_accounts_to_update`] (the pool and its two vaults) and
+//! quotes exact-in swaps through it. Quotes come from [`amm_client::quote`],
+//! which runs the program's own math, so a routed swap pays what was quoted.
+//!
+//! The adapter is quote-only for now. Jupiter's program picks how to invoke a
+//! DEX from the [`Swap`] variant it is handed, and variants are assigned when a
+//! program is listed; none is assigned to this program and the interface has no
+//! generic one, so [`Amm::get_swap_and_account_metas`] returns an error.
+//! [`ConstantProductAmm::swap_account_metas`] builds the accounts the variant
+//! will carry.
+
+use amm::state::Pool;
+use amm_client::accounts::{deserialize, deserialize_pool};
+use amm_client::pda::find_config_address;
+use amm_client::quote::{quote_swap, PoolReserves};
+use amm_client::PoolKeys;
+use anchor_lang::{system_program, ToAccountMetas};
+use anchor_spl::associated_token;
+use anchor_spl::token::{self, TokenAccount};
+use anyhow::{bail, ensure, Result};
+use jupiter_amm_interface::{
+    try_get_account_data, AccountMap, Amm, AmmContext, KeyedAccount, Quote, QuoteParams,
+    SwapAndAccountMetas, SwapMode, SwapParams,
+};
+use rust_decimal::Decimal;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::pubkey::Pubkey;
+
+/// Name shown for routes through these pools
+pub const LABEL: &str = "Constant Product AMM";
+
+/// Accounts in the `swap` instruction, counting the placeholders for the
+/// referrer and the associated token account it is not asked to create
+const SWAP_ACCOUNTS_LEN: usize = 15;
+
+/// A pool as the router sees it: its state and the vault balances last fetched
+#[derive(Clone)]
+pub struct ConstantProductAmm {
+    key: Pubkey,
+    pool: Pool,
+    reserves: PoolReserves,
+}
+
+impl ConstantProductAmm {
+    fn keys(&self) -> PoolKeys {
+        PoolKeys::new(self.key, &self.pool)
+    }
+
+    /// Reject directions other than A to B and B to A
+    fn check_pair(&self, input_mint: &Pubkey, output_mint: &Pubkey) -> Result<()> {
+        let (mint_a, mint_b) = (self.pool.token_mint_a, self.pool.token_mint_b);
+        ensure!(
+            (*input_mint == mint_a && *output_mint == mint_b) || (*input_mint == mint_b && *output_mint == mint_a),
+            "pool {} does not trade {input_mint} for {output_mint}",
+            self.key
+        );
+        Ok(())
+    }
+
+    /// Accounts of the program's `swap` instruction for a routed exact-in swap
+    /// paying into `destination_token_account`
+    pub fn swap_account_metas(&self, swap_params: &SwapParams) -> Result<Vec<AccountMeta>> {
+        self.check_pair(&swap_params.source_mint, &swap_params.destination_mint)?;
+
+        let keys = self.keys();
+        let (vault_input, vault_output, output_mint) = keys.swap_accounts(&swap_params.source_mint);
+        Ok(amm::accounts::Swap {
+            user: swap_params.token_transfer_authority,
+            pool: self.key,
+            config: find_config_address().0,
+            token_mint_a: keys.token_mint_a,
+            token_mint_b: keys.token_mint_b,
+            output_mint,
+            user_input: swap_params.source_token_account,
+            user_output: Some(swap_params.destination_token_account),
+            vault_input,
+            vault_output,
+            referrer_token: None,
+            user_output_ata: None,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None))
+    }
+}
+
+impl Amm for ConstantProductAmm {
+    fn from_keyed_account(keyed_account: &KeyedAccount, _amm_context: &AmmContext) -> Result<Self> {
+        ensure!(
+            keyed_account.account.owner == amm::ID,
+            "{} is not owned by the AMM program",
+            keyed_account.key
+        );
+        Ok(Self {
+            key: keyed_account.key,
+            pool: deserialize_pool(&keyed_account.account.data)?,
+            // Filled in by the first `update`; quoting before it fails as not ready
+            reserves: PoolReserves::default(),
+        })
+    }
+
+    fn label(&self) -> String {
+        LABEL.to_string()
+    }
+
+    fn program_id(&self) -> Pubkey {
+        amm::ID
+    }
+
+    fn key(&self) -> Pubkey {
+        self.key
+    }
+
+    fn get_reserve_mints(&self) -> Vec<Pubkey> {
+        vec![self.pool.token_mint_a, self.pool.token_mint_b]
+    }
+
+    fn get_accounts_to_update(&self) -> Vec<Pubkey> {
+        vec![self.key, self.pool.vault_a, self.pool.vault_b]
+    }
+
+    fn update(&mut self, account_map: &AccountMap) -> Result<()> {
+        self.pool = deserialize_pool(try_get_account_data(account_map, &self.key)?)?;
+        let vault_a: TokenAccount = deserialize(try_get_account_data(account_map, &self.pool.vault_a)?)?;
+        let vault_b: TokenAccount = deserialize(try_get_account_data(account_map, &self.pool.vault_b)?)?;
+        self.reserves = PoolReserves {
+            reserve_a: vault_a.amount,
+            reserve_b: vault_b.amount,
+            // Swap quotes do not depend on the LP supply, so the LP mint is not fetched
+            lp_supply: 0,
+        };
+        Ok(())
+    }
+
+    fn quote(&self, quote_params: &QuoteParams) -> Result<Quote> {
+        ensure!(
+            matches!(quote_params.swap_mode, SwapMode::ExactIn),
+            "{LABEL} only supports exact-in swaps"
+        );
+        self.check_pair(&quote_params.input_mint, &quote_params.output_mint)?;
+
+        let quote = quote_swap(&self.pool, &self.reserves, &quote_params.input_mint, quote_params.amount, 0)?;
+        Ok(Quote {
+            // Only set by venues whose quotes are ranges or exact-out
+            min_in_amount: None,
+            min_out_amount: None,
+            in_amount: quote_params.amount,
+            out_amount: quote.amount_out,
+            fee_amount: quote.fee_amount,
+            fee_mint: quote_params.input_mint,
+            fee_pct: Decimal::new(self.pool.fee_bps.into(), 4),
+        })
+    }
+
+    fn get_swap_and_account_metas(&self, swap_params: &SwapParams) -> Result<SwapAndAccountMetas> {
+        self.swap_account_metas(swap_params)?;
+        bail!("{LABEL} has no Jupiter swap variant assigned yet, so routes through it cannot be built")
+    }
+
+    fn clone_amm(&self) -> Box<dyn Amm + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn is_active(&self) -> bool {
+        !self.pool.is_emergency_mode()
+    }
+
+    fn get_accounts_len(&self) -> usize {
+        SWAP_ACCOUNTS_LEN
+    }
+}
@@ -0,0 +1,217 @@
+//! The adapter against account snapshots in the format written by
+//! `solana account <ADDRESS> --output json`: a pool with 1,000 A and 4,000 B
+//! (6 decimals) in its vaults and the default 30 bps fee. The snapshots are
+//! taken from a pool the program created in `amm-program-tests`; see
+//! `tests/jupiter_fixtures.rs` there to regenerate them.
+
+use std::str::FromStr;
+
+use amm::error::AmmError;
+use amm::math::get_amount_out;
+use amm_client::accounts::deserialize_pool;
+use amm_client::instructions::{self, args};
+use amm_client::PoolKeys;
+use amm_jupiter::{ConstantProductAmm, LABEL};
+use anchor_spl::associated_token::get_associated_token_address;
+use base64::Engine;
+use jupiter_amm_interface::{
+    AccountMap, Amm, AmmContext, ClockRef, KeyedAccount, QuoteParams, SwapMode, SwapParams,
+};
+use rust_decimal::Decimal;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+
+const RESERVE_A: u64 = 1_000_000_000;
+const RESERVE_B: u64 = 4_000_000_000;
+
+fn snapshot(name: &str) -> KeyedAccount {
+    let path = format!("{}/tests/fixtures/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    let account = &json["account"];
+    let data = base64::engine::general_purpose::STANDARD
+        .decode(account["data"][0].as_str().unwrap())
+        .unwrap();
+
+    KeyedAccount {
+        key: Pubkey::from_str(json["pubkey"].as_str().unwrap()).unwrap(),
+        account: Account {
+            lamports: account["lamports"].as_u64().unwrap(),
+            data,
+            owner: Pubkey::from_str(account["owner"].as_str().unwrap()).unwrap(),
+            executable: false,
+            rent_epoch: account["rentEpoch"].as_u64().unwrap(),
+        },
+        params: None,
+    }
+}
+
+fn context() -> AmmContext {
+    AmmContext {
+        clock_ref: ClockRef::default(),
+    }
+}
+
+/// The pool loaded and updated from the snapshot, with its keys
+fn loaded_amm() -> (ConstantProductAmm, PoolKeys) {
+    let pool = snapshot("pool");
+    let keys = PoolKeys::new(pool.key, &deserialize_pool(&pool.account.data).unwrap());
+    let mut amm = ConstantProductAmm::from_keyed_account(&pool, &context()).unwrap();
+
+    let mut account_map = AccountMap::default();
+    for account in [pool, snapshot("vault_a"), snapshot("vault_b")] {
+        account_map.insert(account.key, account.account);
+    }
+    amm.update(&account_map).unwrap();
+    (amm, keys)
+}
+
+fn quote_params(input_mint: Pubkey, output_mint: Pubkey, amount: u64) -> QuoteParams {
+    QuoteParams {
+        amount,
+        input_mint,
+        output_mint,
+        swap_mode: SwapMode::ExactIn,
+    }
+}
+
+fn swap_params(user: &Pubkey, input_mint: Pubkey, output_mint: Pubkey) -> SwapParams<'static, 'static> {
+    SwapParams {
+        swap_mode: SwapMode::ExactIn,
+        in_amount: 10_000_000,
+        out_amount: 0,
+        source_mint: input_mint,
+        destination_mint: output_mint,
+        source_token_account: get_associated_token_address(user, &input_mint),
+        destination_token_account: get_associated_token_address(user, &output_mint),
+        token_transfer_authority: *user,
+        open_order_address: None,
+        quote_mint_to_referrer: None,
+        jupiter_program_id: &amm::ID,
+        missing_dynamic_accounts_as_default: false,
+    }
+}
+
+#[test]
+fn loads_pool_snapshot() {
+    let (amm, keys) = loaded_amm();
+
+    assert_eq!(amm.label(), LABEL);
+    assert_eq!(amm.program_id(), amm::ID);
+    assert_eq!(amm.get_reserve_mints(), vec![keys.token_mint_a, keys.token_mint_b]);
+    assert_eq!(amm.get_accounts_to_update(), vec![keys.pool, keys.vault_a, keys.vault_b]);
+    assert!(amm.is_active());
+}
+
+#[test]
+fn rejects_accounts_of_other_programs() {
+    let mut vault = snapshot("vault_a");
+    assert!(ConstantProductAmm::from_keyed_account(&vault, &context()).is_err());
+
+    // Right owner, wrong account type
+    vault.account.owner = amm::ID;
+    assert!(ConstantProductAmm::from_keyed_account(&vault, &context()).is_err());
+}
+
+#[test]
+fn quotes_with_program_math() {
+    let (amm, keys) = loaded_amm();
+
+    let quote = amm.quote(&quote_params(keys.token_mint_a, keys.token_mint_b, 10_000_000)).unwrap();
+    assert_eq!(quote.in_amount, 10_000_000);
+    assert_eq!(quote.out_amount, get_amount_out(10_000_000, RESERVE_A, RESERVE_B, 30).unwrap());
+    assert_eq!(quote.fee_amount, 30_000);
+    assert_eq!(quote.fee_mint, keys.token_mint_a);
+    assert_eq!(quote.fee_pct, Decimal::new(30, 4));
+
+    let quote = amm.quote(&quote_params(keys.token_mint_b, keys.token_mint_a, 10_000_000)).unwrap();
+    assert_eq!(quote.out_amount, get_amount_out(10_000_000, RESERVE_B, RESERVE_A, 30).unwrap());
+    assert_eq!(quote.fee_mint, keys.token_mint_b);
+}
+
+#[test]
+fn quote_errors() {
+    let (amm, keys) = loaded_amm();
+
+    let mut exact_out = quote_params(keys.token_mint_a, keys.token_mint_b, 10_000_000);
+    exact_out.swap_mode = SwapMode::ExactOut;
+    assert!(amm.quote(&exact_out).is_err());
+
+    assert!(amm.quote(&quote_params(keys.token_mint_a, keys.lp_mint, 10_000_000)).is_err());
+    assert!(amm.quote(&quote_params(keys.token_mint_a, keys.token_mint_a, 10_000_000)).is_err());
+
+    let error = amm.quote(&quote_params(keys.token_mint_a, keys.token_mint_b, 0)).unwrap_err();
+    assert_eq!(error.downcast::<anchor_lang::error::Error>().unwrap(), AmmError::ZeroLiquidity.into());
+
+    // Nothing is quoted before the vaults have been read
+    let fresh = ConstantProductAmm::from_keyed_account(&snapshot("pool"), &context()).unwrap();
+    let error = fresh.quote(&quote_params(keys.token_mint_a, keys.token_mint_b, 10_000_000)).unwrap_err();
+    assert_eq!(error.downcast::<anchor_lang::error::Error>().unwrap(), AmmError::PoolNotReady.into());
+}
+
+#[test]
+fn routes_wait_for_a_swap_variant() {
+    let (amm, keys) = loaded_amm();
+    let user = Pubkey::new_unique();
+
+    let Err(error) = amm.get_swap_and_account_metas(&swap_params(&user, keys.token_mint_a, keys.token_mint_b)) else {
+        panic!("routes should not be built without a swap variant");
+    };
+    assert!(error.to_string().contains("no Jupiter swap variant"));
+}
+
+#[test]
+fn swap_metas_match_the_client_builder() {
+    let (amm, keys) = loaded_amm();
+    let user = Pubkey::new_unique();
+
+    for (input_mint, output_mint) in [(keys.token_mint_a, keys.token_mint_b), (keys.token_mint_b, keys.token_mint_a)] {
+        let account_metas = amm.swap_account_metas(&swap_params(&user, input_mint, output_mint)).unwrap();
+        let destination = get_associated_token_address(&user, &output_mint);
+        let expected = instructions::swap_to(&user, &keys, &input_mint, &destination, None, args::Swap {
+            amount_in: 10_000_000,
+            minimum_amount_out: 0,
+            referral_bps: 0,
+            deadline: None,
+        });
+        assert_eq!(account_metas, expected.accounts);
+        assert_eq!(account_metas.len(), amm.get_accounts_len());
+    }
+}
+
+#[test]
+fn swap_metas_match_the_client_swap() {
+    let (amm, keys) = loaded_amm();
+    let user = Pubkey::new_unique();
+
+    for (input_mint, output_mint) in [(keys.token_mint_a, keys.token_mint_b), (keys.token_mint_b, keys.token_mint_a)] {
+        let account_metas = amm.swap_account_metas(&swap_params(&user, input_mint, output_mint)).unwrap();
+        let mut expected = instructions::swap(&user, &keys, &input_mint, None, args::Swap {
+            amount_in: 10_000_000,
+            minimum_amount_out: 0,
+            referral_bps: 0,
+            deadline: None,
+        })
+        .accounts;
+        // The client pays into `user_output_ata`, the adapter into `user_output`;
+        // every other account is the same
+        assert_eq!(expected[11].pubkey, get_associated_token_address(&user, &output_mint));
+        expected.swap(7, 11);
+        assert_eq!(account_metas, expected);
+    }
+}
+
+#[test]
+fn swap_metas_pass_the_destination_through() {
+    let (amm, keys) = loaded_amm();
+    let user = Pubkey::new_unique();
+
+    let foreign = swap_params(&user, keys.token_mint_a, keys.lp_mint);
+    assert!(amm.swap_account_metas(&foreign).is_err());
+
+    // Routes may pay into an intermediate account that is not an associated token account
+    let mut intermediate = swap_params(&user, keys.token_mint_a, keys.token_mint_b);
+    intermediate.destination_token_account = Pubkey::new_unique();
+    let account_metas = amm.swap_account_metas(&intermediate).unwrap();
+    assert_eq!(account_metas[7].pubkey, intermediate.destination_token_account);
+    assert!(account_metas[7].is_writable);
+}
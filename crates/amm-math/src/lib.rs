@@ -0,0 +1,528 @@
+//! Constant product AMM math without Anchor or std.
+//!
+//! The on-chain program wraps these functions in `amm::math`, mapping
+//! [`MathError`] into its own error type, so off-chain users get bit-identical
+//! results without depending on Anchor.
+
+#![no_std]
+
+use core::fmt;
+
+#[allow(clippy::all)]
+mod wide {
+    uint::construct_uint! {
+        /// 256-bit unsigned integer for intermediates that overflow u128
+        pub struct U256(4);
+    }
+}
+
+pub use wide::U256;
+
+/// Errors returned by the AMM math
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MathError {
+    /// An intermediate or result does not fit its type
+    Overflow,
+    /// Reserves or supply are too small for the operation
+    InsufficientLiquidity,
+    /// An amount that must be positive is zero
+    ZeroLiquidity,
+    /// Amounts fall outside the caller's bounds
+    SlippageExceeded,
+}
+
+impl fmt::Display for MathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            MathError::Overflow => "Math operation overflow",
+            MathError::InsufficientLiquidity => "Pool has insufficient liquidity",
+            MathError::ZeroLiquidity => "Liquidity amount cannot be zero",
+            MathError::SlippageExceeded => "Slippage tolerance exceeded",
+        })
+    }
+}
+
+pub type Result<T> = core::result::Result<T, MathError>;
+
+macro_rules! ensure {
+    ($cond:expr, $err:expr $(,)?) => {
+        if !$cond {
+            return Err($err);
+        }
+    };
+}
+
+/// Calculate proportional amount based on reserves
+pub fn quote(
+    amount_a: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+) -> Result<u64> {
+    ensure!(reserve_a > 0, MathError::InsufficientLiquidity);
+    
+    let amount_b = (amount_a as u128)
+        .checked_mul(reserve_b as u128)
+        .ok_or(MathError::Overflow)?
+        .checked_div(reserve_a as u128)
+        .ok_or(MathError::Overflow)?;
+    
+    u64::try_from(amount_b).map_err(|_| MathError::Overflow)
+}
+
+/// Calculate output amount with fee deduction using constant product formula
+pub fn get_amount_out(
+    amount_in: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    fee_bps: u16,
+) -> Result<u64> {
+    ensure!(reserve_in > 0 && reserve_out > 0, MathError::InsufficientLiquidity);
+    ensure!(amount_in > 0, MathError::ZeroLiquidity);
+    
+    let fee_complement = 10_000u128
+        .checked_sub(fee_bps as u128)
+        .ok_or(MathError::Overflow)?;
+    
+    let amount_in_with_fee = (amount_in as u128)
+        .checked_mul(fee_complement)
+        .ok_or(MathError::Overflow)?;
+    
+    let numerator = (reserve_out as u128)
+        .checked_mul(amount_in_with_fee)
+        .ok_or(MathError::Overflow)?;
+    
+    let denominator = (reserve_in as u128)
+        .checked_mul(10_000u128)
+        .ok_or(MathError::Overflow)?
+        .checked_add(amount_in_with_fee)
+        .ok_or(MathError::Overflow)?;
+    
+    let amount_out = numerator
+        .checked_div(denominator)
+        .ok_or(MathError::Overflow)?;
+    
+    u64::try_from(amount_out).map_err(|_| MathError::Overflow)
+}
+
+/// Calculate integer square root using Newton's method
+pub fn integer_sqrt(value: u128) -> u128 {
+    if value == 0 {
+        return 0;
+    }
+    
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    
+    x
+}
+
+/// Calculate how much of a single-token deposit to swap so that the remainder
+/// and the swap output match the post-swap reserve ratio.
+///
+/// Solves `(1 - f) * s^2 + (2 - f) * r * s - a * r = 0` for `s`, where `a` is the
+/// deposit, `r` the input-side reserve and `f` the fee. With `F = 10_000 - fee_bps`
+/// and `D = 10_000` the positive root is
+/// `s = (sqrt(((D + F) * r)^2 + 4 * D * F * a * r) - (D + F) * r) / (2 * F)`.
+pub fn get_single_sided_swap_amount(
+    amount_in: u64,
+    reserve_in: u64,
+    fee_bps: u16,
+) -> Result<u64> {
+    ensure!(reserve_in > 0, MathError::InsufficientLiquidity);
+    ensure!(amount_in > 0, MathError::ZeroLiquidity);
+    
+    let fee_complement = 10_000u128
+        .checked_sub(fee_bps as u128)
+        .ok_or(MathError::Overflow)?;
+    ensure!(fee_complement > 0, MathError::Overflow);
+    
+    let b = U256::from(10_000u128 + fee_complement) * U256::from(reserve_in);
+    let four_ac = U256::from(4u128 * 10_000u128 * fee_complement)
+        * U256::from(amount_in)
+        * U256::from(reserve_in);
+    
+    let swap_amount = ((b * b + four_ac).integer_sqrt() - b) / U256::from(2 * fee_complement);
+    
+    u64::try_from(swap_amount.as_u128()).map_err(|_| MathError::Overflow)
+}
+
+/// Calculate `amount * numerator / denominator`, rounding up
+pub fn mul_div_ceil(
+    amount: u64,
+    numerator: u64,
+    denominator: u64,
+) -> Result<u64> {
+    ensure!(denominator > 0, MathError::InsufficientLiquidity);
+    
+    let result = (amount as u128)
+        .checked_mul(numerator as u128)
+        .ok_or(MathError::Overflow)?
+        .div_ceil(denominator as u128);
+    
+    u64::try_from(result).map_err(|_| MathError::Overflow)
+}
+
+/// Calculate the LP tokens to burn for withdrawing exact token amounts, rounding up.
+///
+/// The part of the withdrawal matching the reserve ratio is charged pro-rata.
+/// The excess on the over-withdrawn side is grossed up by the swap fee, which
+/// stays in the pool, and charged by the relative drop in `sqrt(k)` it causes,
+/// the same as a proportional withdrawal followed by a swap back into that side.
+pub fn get_lp_for_exact_withdrawal(
+    amount_a: u64,
+    amount_b: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+    fee_bps: u16,
+) -> Result<u64> {
+    ensure!(reserve_a > 0 && reserve_b > 0 && lp_supply > 0, MathError::InsufficientLiquidity);
+    ensure!(amount_a > 0 || amount_b > 0, MathError::ZeroLiquidity);
+    ensure!(amount_a <= reserve_a && amount_b <= reserve_b, MathError::InsufficientLiquidity);
+    
+    let fee_complement = 10_000u64
+        .checked_sub(fee_bps as u64)
+        .ok_or(MathError::Overflow)?;
+    ensure!(fee_complement > 0, MathError::Overflow);
+    
+    // Split into a balanced part and an excess on whichever side is over-withdrawn
+    let a_is_balanced = (amount_a as u128) * (reserve_b as u128)
+        <= (amount_b as u128) * (reserve_a as u128);
+    let (balanced, reserve_balanced, excess, reserve_excess, reserve_other) = if a_is_balanced {
+        let proportional_b = quote(amount_a, reserve_a, reserve_b)?;
+        (amount_a, reserve_a, amount_b - proportional_b, reserve_b - proportional_b, reserve_a - amount_a)
+    } else {
+        let proportional_a = quote(amount_b, reserve_b, reserve_a)?;
+        (amount_b, reserve_b, amount_a - proportional_a, reserve_a - proportional_a, reserve_b - amount_b)
+    };
+    
+    let lp_balanced = mul_div_ceil(balanced, lp_supply, reserve_balanced)?;
+    if excess == 0 {
+        ensure!(lp_balanced <= lp_supply, MathError::InsufficientLiquidity);
+        return Ok(lp_balanced);
+    }
+    
+    let fee = mul_div_ceil(excess, fee_bps as u64, fee_complement)?;
+    let effective_excess = excess.checked_add(fee).ok_or(MathError::Overflow)?;
+    ensure!(effective_excess < reserve_excess, MathError::InsufficientLiquidity);
+    ensure!(reserve_other > 0, MathError::InsufficientLiquidity);
+    
+    let lp_remaining = lp_supply - lp_balanced;
+    let k_before = (reserve_other as u128) * (reserve_excess as u128);
+    let k_after = (reserve_other as u128) * ((reserve_excess - effective_excess) as u128);
+    
+    // Round sqrt(k) before up and after down so the burn favours the pool
+    let mut sqrt_k_before = integer_sqrt(k_before);
+    if sqrt_k_before * sqrt_k_before < k_before {
+        sqrt_k_before += 1;
+    }
+    let sqrt_k_after = integer_sqrt(k_after);
+    
+    let lp_excess = (lp_remaining as u128)
+        .checked_mul(sqrt_k_before - sqrt_k_after)
+        .ok_or(MathError::Overflow)?
+        .div_ceil(sqrt_k_before);
+    
+    let lp_amount = (lp_balanced as u128)
+        .checked_add(lp_excess)
+        .ok_or(MathError::Overflow)?;
+    let lp_amount = u64::try_from(lp_amount).map_err(|_| MathError::Overflow)?;
+    
+    // A tiny excess can round to nothing, and the burn can never exceed the supply
+    ensure!(lp_amount > 0, MathError::ZeroLiquidity);
+    ensure!(lp_amount <= lp_supply, MathError::InsufficientLiquidity);
+    Ok(lp_amount)
+}
+
+/// Calculate the token amounts to pay for minting exactly `lp_out`, each
+/// rounded up so the pool never mints LP below its share
+pub fn get_exact_lp_deposit_amounts(
+    lp_out: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+) -> Result<(u64, u64)> {
+    ensure!(lp_out > 0, MathError::ZeroLiquidity);
+    ensure!(reserve_a > 0 && reserve_b > 0 && lp_supply > 0, MathError::InsufficientLiquidity);
+    
+    Ok((
+        mul_div_ceil(lp_out, reserve_a, lp_supply)?,
+        mul_div_ceil(lp_out, reserve_b, lp_supply)?,
+    ))
+}
+
+/// Calculate the LP tokens minted for a single-token deposit of `amount_in`.
+///
+/// The optimal fraction is swapped into the other token and the remainder is
+/// deposited together with the swap output at the post-swap reserves.
+pub fn get_single_sided_deposit_lp(
+    amount_in: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    lp_supply: u64,
+    fee_bps: u16,
+) -> Result<u64> {
+    ensure!(amount_in > 0, MathError::ZeroLiquidity);
+    ensure!(reserve_in > 0 && reserve_out > 0 && lp_supply > 0, MathError::InsufficientLiquidity);
+    
+    let swap_amount = get_single_sided_swap_amount(amount_in, reserve_in, fee_bps)?;
+    let swap_out = get_amount_out(swap_amount, reserve_in, reserve_out, fee_bps)?;
+    ensure!(swap_out < reserve_out, MathError::InsufficientLiquidity);
+    
+    let deposit_in = amount_in
+        .checked_sub(swap_amount)
+        .ok_or(MathError::Overflow)?;
+    let reserve_in_after = reserve_in
+        .checked_add(swap_amount)
+        .ok_or(MathError::Overflow)?;
+    let reserve_out_after = reserve_out - swap_out;
+    
+    let lp_in = (deposit_in as u128)
+        .checked_mul(lp_supply as u128)
+        .ok_or(MathError::Overflow)?
+        / reserve_in_after as u128;
+    
+    let lp_out = (swap_out as u128)
+        .checked_mul(lp_supply as u128)
+        .ok_or(MathError::Overflow)?
+        / reserve_out_after as u128;
+    
+    let lp_tokens = u64::try_from(lp_in.min(lp_out)).map_err(|_| MathError::Overflow)?;
+    ensure!(lp_tokens > 0, MathError::ZeroLiquidity);
+    
+    Ok(lp_tokens)
+}
+
+/// Calculate the amount of one token received for burning `lp_amount`.
+///
+/// The pro-rata share of the other token is swapped back through the pool at
+/// the post-withdrawal reserves, so withdrawing the whole pool is refused.
+pub fn get_single_sided_withdraw_amount(
+    lp_amount: u64,
+    reserve_out: u64,
+    reserve_other: u64,
+    lp_supply: u64,
+    fee_bps: u16,
+) -> Result<u64> {
+    ensure!(lp_amount > 0, MathError::ZeroLiquidity);
+    ensure!(lp_supply > 0 && lp_amount <= lp_supply, MathError::InsufficientLiquidity);
+    
+    let share_out = quote(lp_amount, lp_supply, reserve_out)?;
+    let share_other = quote(lp_amount, lp_supply, reserve_other)?;
+    ensure!(share_out > 0 && share_other > 0, MathError::InsufficientLiquidity);
+    
+    let reserve_out_after = reserve_out - share_out;
+    let reserve_other_after = reserve_other - share_other;
+    ensure!(reserve_out_after > 0 && reserve_other_after > 0, MathError::InsufficientLiquidity);
+    
+    let swap_out = get_amount_out(share_other, reserve_other_after, reserve_out_after, fee_bps)?;
+    ensure!(swap_out < reserve_out_after, MathError::InsufficientLiquidity);
+    
+    share_out.checked_add(swap_out).ok_or(MathError::Overflow)
+}
+
+/// Calculate deposit amounts and LP tokens to mint for an add_liquidity request.
+///
+/// The first deposit uses both maximums and sets the price. Later deposits are
+/// trimmed to the reserve ratio so no surplus is gifted to existing LPs.
+pub fn get_deposit_amounts(
+    amount_a_max: u64,
+    amount_b_max: u64,
+    amount_a_min: u64,
+    amount_b_min: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+) -> Result<(u64, u64, u64)> {
+    if lp_supply == 0 {
+        // First liquidity provision sets the price, so both maximums are used
+        let liquidity = integer_sqrt((amount_a_max as u128).checked_mul(amount_b_max as u128).ok_or(MathError::Overflow)?);
+        let lp = u64::try_from(liquidity).map_err(|_| MathError::Overflow)?;
+        ensure!(lp > 0, MathError::ZeroLiquidity);
+        Ok((amount_a_max, amount_b_max, lp))
+    } else {
+        // Subsequent liquidity provision - only the amounts matching the reserve
+        // ratio are taken, the rest stays with the user
+        ensure!(reserve_a > 0, MathError::InsufficientLiquidity);
+        
+        // A binds when quote(amount_a_max) <= amount_b_max, decided without
+        // quoting so that a maximum on the other side is never priced and
+        // u64::MAX can stand for "whatever the other side allows"
+        let a_binds = (amount_a_max as u128) * (reserve_b as u128)
+            < (amount_b_max as u128 + 1) * (reserve_a as u128);
+        let (amount_a, amount_b) = if a_binds {
+            let amount_b_optimal = quote(amount_a_max, reserve_a, reserve_b)?;
+            ensure!(amount_b_optimal >= amount_b_min, MathError::SlippageExceeded);
+            (amount_a_max, amount_b_optimal)
+        } else {
+            let amount_a_optimal = quote(amount_b_max, reserve_b, reserve_a)?;
+            ensure!(amount_a_optimal >= amount_a_min, MathError::SlippageExceeded);
+            (amount_a_optimal, amount_b_max)
+        };
+        ensure!(amount_a > 0 && amount_b > 0, MathError::ZeroLiquidity);
+        
+        let lp_a = (amount_a as u128)
+            .checked_mul(lp_supply as u128)
+            .ok_or(MathError::Overflow)?
+            .checked_div(reserve_a as u128)
+            .ok_or(MathError::Overflow)?;
+        
+        let lp_b = (amount_b as u128)
+            .checked_mul(lp_supply as u128)
+            .ok_or(MathError::Overflow)?
+            .checked_div(reserve_b as u128)
+            .ok_or(MathError::Overflow)?;
+        
+        let lp = lp_a.min(lp_b);
+        Ok((amount_a, amount_b, u64::try_from(lp).map_err(|_| MathError::Overflow)?))
+    }
+}
+
+/// Calculate proportional withdrawal amounts for burning LP tokens
+pub fn get_withdraw_amounts(
+    lp_amount: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+) -> Result<(u64, u64)> {
+    ensure!(lp_supply > 0, MathError::InsufficientLiquidity);
+    ensure!(lp_amount <= lp_supply, MathError::InsufficientLiquidity);
+    
+    let amount_a = (lp_amount as u128)
+        .checked_mul(reserve_a as u128)
+        .ok_or(MathError::Overflow)?
+        .checked_div(lp_supply as u128)
+        .ok_or(MathError::Overflow)?;
+    
+    let amount_b = (lp_amount as u128)
+        .checked_mul(reserve_b as u128)
+        .ok_or(MathError::Overflow)?
+        .checked_div(lp_supply as u128)
+        .ok_or(MathError::Overflow)?;
+    
+    let amount_a = u64::try_from(amount_a).map_err(|_| MathError::Overflow)?;
+    let amount_b = u64::try_from(amount_b).map_err(|_| MathError::Overflow)?;
+    
+    ensure!(amount_a > 0 && amount_b > 0, MathError::InsufficientLiquidity);
+    ensure!(amount_a <= reserve_a && amount_b <= reserve_b, MathError::InsufficientLiquidity);
+    
+    Ok((amount_a, amount_b))
+}
+
+/// Fixed-point scale for farm reward-per-share accumulators
+pub const REWARD_PRECISION: u128 = 1_000_000_000_000;
+
+/// Advance a farm's reward-per-share accumulator by `elapsed` seconds of emissions
+pub fn get_acc_reward_per_share(
+    acc_reward_per_share: u128,
+    reward_per_second: u64,
+    elapsed: u64,
+    total_staked: u64,
+) -> Result<u128> {
+    // Emissions with nobody staked are left undistributed in the vault
+    if total_staked == 0 {
+        return Ok(acc_reward_per_share);
+    }
+    
+    let increment = (reward_per_second as u128)
+        .checked_mul(elapsed as u128)
+        .ok_or(MathError::Overflow)?
+        .checked_mul(REWARD_PRECISION)
+        .ok_or(MathError::Overflow)?
+        .checked_div(total_staked as u128)
+        .ok_or(MathError::Overflow)?;
+    
+    acc_reward_per_share
+        .checked_add(increment)
+        .ok_or(MathError::Overflow)
+}
+
+/// Calculate the reward checkpoint for a staked amount, in reward token units
+pub fn get_reward_debt(
+    staked_amount: u64,
+    acc_reward_per_share: u128,
+) -> Result<u128> {
+    let debt = (staked_amount as u128)
+        .checked_mul(acc_reward_per_share)
+        .ok_or(MathError::Overflow)?
+        / REWARD_PRECISION;
+    
+    Ok(debt)
+}
+
+/// Calculate rewards earned by a staked amount since its last checkpoint
+pub fn get_pending_reward(
+    staked_amount: u64,
+    acc_reward_per_share: u128,
+    reward_debt: u128,
+) -> Result<u64> {
+    let pending = get_reward_debt(staked_amount, acc_reward_per_share)?
+        .checked_sub(reward_debt)
+        .ok_or(MathError::Overflow)?;
+    
+    u64::try_from(pending).map_err(|_| MathError::Overflow)
+}
+
+/// Fixed-point scale for LP fee growth
+pub const FEE_GROWTH_PRECISION: u128 = 1_000_000_000_000;
+
+/// Pool value per LP token as `sqrt(reserve_a * reserve_b) / lp_supply`, scaled
+/// by `FEE_GROWTH_PRECISION`. Swap fees stay in the reserves, so this only grows
+/// as fees are collected and is unaffected by price moves.
+pub fn get_sqrt_k_per_lp(
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+) -> Result<u128> {
+    if lp_supply == 0 {
+        return Ok(0);
+    }
+    
+    let sqrt_k = integer_sqrt((reserve_a as u128) * (reserve_b as u128));
+    
+    sqrt_k
+        .checked_mul(FEE_GROWTH_PRECISION)
+        .ok_or(MathError::Overflow)?
+        .checked_div(lp_supply as u128)
+        .ok_or(MathError::Overflow)
+}
+
+/// Portion of a redeemable amount earned from fees since the position entered
+/// at `entry_sqrt_k_per_lp`
+pub fn get_fees_earned(
+    amount: u64,
+    entry_sqrt_k_per_lp: u128,
+    sqrt_k_per_lp: u128,
+) -> Result<u64> {
+    if sqrt_k_per_lp <= entry_sqrt_k_per_lp {
+        return Ok(0);
+    }
+    
+    let fees = (amount as u128)
+        .checked_mul(sqrt_k_per_lp - entry_sqrt_k_per_lp)
+        .ok_or(MathError::Overflow)?
+        / sqrt_k_per_lp;
+    
+    u64::try_from(fees).map_err(|_| MathError::Overflow)
+}
+
+/// Impermanent loss in basis points of the value the deposited tokens would
+/// have if simply held. Both values must be in the same unit and exclude fees.
+/// Negative when the position is worth more than holding.
+pub fn get_impermanent_loss_bps(hold_value: u64, position_value: u64) -> Result<i64> {
+    if hold_value == 0 {
+        return Ok(0);
+    }
+    
+    let loss = (hold_value as i128 - position_value as i128)
+        .checked_mul(10_000)
+        .ok_or(MathError::Overflow)?
+        / hold_value as i128;
+    
+    i64::try_from(loss).map_err(|_| MathError::Overflow)
+}
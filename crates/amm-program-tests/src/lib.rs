@@ -0,0 +1,425 @@
+//! In-process test harness for the AMM program.
+//!
+//! The program runs inside `solana-program-test`'s bank together with the real
+//! SPL Token and Associated Token programs. When `SBF_OUT_DIR` (or
+//! `BPF_OUT_DIR`) points at a directory holding `amm.so`, as it does in CI after
+//! `cargo build-sbf`, the compiled program is loaded. Otherwise the program is
+//! linked in natively, which keeps `cargo test` working without the Solana
+//! toolchain but does not exercise the SBF build.
+//!
+//! The suites under `tests/` run every instruction and reach every `AmmError`
+//! except two: `PoolAlreadyExists` is never returned (re-initializing a pool
+//! fails in the system program first), and `MathOverflow` needs amounts near
+//! `u64::MAX`, so it is left to the property tests in `programs/amm/tests`
+//! except for a farm whose reward accumulator is rewritten to overflow.
+//! Account constraints are checked for the main instructions, not for every
+//! account of every instruction.
+
+use std::path::PathBuf;
+
+use amm::error::AmmError;
+use amm::instructions::PositionStats;
+use amm_client::instructions::{self, args};
+use amm_client::{pda, PoolKeys};
+use anchor_lang::{AccountDeserialize, AccountSerialize, AnchorDeserialize};
+use anchor_spl::token::spl_token::{self, native_mint};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::account::Account;
+use solana_sdk::account_info::AccountInfo;
+use solana_sdk::clock::Clock;
+use solana_sdk::entrypoint::ProgramResult;
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::program_option::COption;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+
+pub use amm_client;
+
+/// Initial reserves of the pool built by [`TestEnv::seeded_pool`]
+pub const SEED_A: u64 = 1_000_000_000;
+pub const SEED_B: u64 = 4_000_000_000;
+
+/// Directory holding the compiled `amm.so`, if one was built
+pub fn compiled_program_dir() -> Option<PathBuf> {
+    ["SBF_OUT_DIR", "BPF_OUT_DIR"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(PathBuf::from)
+        .find(|dir| dir.join("amm.so").exists())
+}
+
+/// Adapt Anchor's entry point to the builtin signature program-test expects
+fn process_instruction(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    amm::entry(program_id, accounts, data)
+}
+
+pub type TxResult = Result<(), BanksClientError>;
+
+/// Assert that a transaction failed with `error`
+pub fn assert_amm_error(result: TxResult, error: AmmError) {
+    assert_eq!(custom_error(result), u32::from(error), "expected {error:?}");
+}
+
+/// Custom program error code of a failed transaction
+pub fn custom_error(result: TxResult) -> u32 {
+    match result.expect_err("transaction should fail").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => code,
+        err => panic!("expected a custom program error, got {err:?}"),
+    }
+}
+
+pub struct TestEnv {
+    pub context: ProgramTestContext,
+}
+
+impl TestEnv {
+    pub async fn new() -> Self {
+        // Native linking is only a fallback for machines without cargo-build-sbf
+        let mut program_test = match compiled_program_dir() {
+            Some(_) => ProgramTest::new("amm", amm::ID, None),
+            None => ProgramTest::new("amm", amm::ID, processor!(process_instruction)),
+        };
+
+        // The native instructions need the wrapped SOL mint
+        let mut native_mint_data = vec![0; spl_token::state::Mint::LEN];
+        spl_token::state::Mint {
+            mint_authority: COption::None,
+            supply: 0,
+            decimals: 9,
+            is_initialized: true,
+            freeze_authority: COption::None,
+        }
+        .pack_into_slice(&mut native_mint_data);
+        program_test.add_account(
+            native_mint::ID,
+            Account {
+                lamports: Rent::default().minimum_balance(native_mint_data.len()),
+                data: native_mint_data,
+                owner: spl_token::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        );
+
+        Self {
+            context: program_test.start_with_context().await,
+        }
+    }
+
+    /// The fee payer, which is also the config admin and every mint's authority
+    pub fn payer(&self) -> Keypair {
+        self.context.payer.insecure_clone()
+    }
+
+    async fn transaction(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Transaction {
+        let payer = self.payer();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+
+        let blockhash = self.context.banks_client.get_latest_blockhash().await.unwrap();
+        Transaction::new_signed_with_payer(instructions, Some(&payer.pubkey()), &all_signers, blockhash)
+    }
+
+    pub async fn process(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> TxResult {
+        let tx = self.transaction(instructions, signers).await;
+        self.context.banks_client.process_transaction(tx).await
+    }
+
+    /// Process a transaction that must succeed and return the compute units it consumed
+    pub async fn process_metered(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> u64 {
+        let tx = self.transaction(instructions, signers).await;
+        let result = self.context.banks_client.process_transaction_with_metadata(tx).await.unwrap();
+        result.result.unwrap();
+        result.metadata.expect("transaction metadata missing").compute_units_consumed
+    }
+
+    /// Simulate a view instruction and decode its return data
+    pub async fn simulate<T: AnchorDeserialize>(&mut self, instruction: Instruction) -> T {
+        let tx = self.transaction(&[instruction], &[]).await;
+        let result = self.context.banks_client.simulate_transaction(tx).await.unwrap();
+
+        let return_data = result
+            .simulation_details
+            .and_then(|details| details.return_data)
+            .expect("instruction returned no data");
+        T::try_from_slice(&return_data.data).unwrap()
+    }
+
+    pub async fn new_user(&mut self) -> Keypair {
+        let user = Keypair::new();
+        let payer = self.payer();
+        self.process(&[system_instruction::transfer(&payer.pubkey(), &user.pubkey(), 10_000_000_000)], &[])
+            .await
+            .unwrap();
+        user
+    }
+
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.context.banks_client.get_balance(*address).await.unwrap()
+    }
+
+    pub async fn account_exists(&mut self, address: &Pubkey) -> bool {
+        self.context.banks_client.get_account(*address).await.unwrap().is_some()
+    }
+
+    pub async fn get_account<T: AccountDeserialize>(&mut self, address: &Pubkey) -> T {
+        let account = self.context.banks_client.get_account(*address).await.unwrap().expect("account not found");
+        T::try_deserialize(&mut account.data.as_slice()).unwrap()
+    }
+
+    /// Overwrite the state of an Anchor account, keeping its size and rent
+    pub async fn set_account<T: AccountSerialize>(&mut self, address: &Pubkey, state: &T) {
+        let mut account = self.context.banks_client.get_account(*address).await.unwrap().expect("account not found");
+        let mut data = vec![];
+        state.try_serialize(&mut data).unwrap();
+        account.data[..data.len()].copy_from_slice(&data);
+        self.context.set_account(address, &account.into());
+    }
+
+    /// Rewrite the config as it was stored before `version` and `reserved`
+    pub async fn downgrade_config(&mut self) {
+        self.truncate_account(&pda::find_config_address().0, 8 + 35, 8 + 8 + 35).await;
+    }
+
+    /// Rewrite a pool as it was stored before the zero-copy layout, when it was
+    /// Borsh-encoded without `version`, padding or `reserved`
+    pub async fn downgrade_pool(&mut self, pool: &Pubkey) {
+        self.truncate_account(pool, 8 + 164, 8 + 8 + 164).await;
+    }
+
+    /// Keep the first `used` bytes of an account, zero the rest up to `len` and
+    /// leave it with just enough rent for `len` bytes
+    async fn truncate_account(&mut self, address: &Pubkey, used: usize, len: usize) {
+        let mut account = self.context.banks_client.get_account(*address).await.unwrap().expect("account not found");
+        account.data.truncate(len);
+        account.data[used..].fill(0);
+        account.lamports = Rent::default().minimum_balance(len);
+        self.context.set_account(address, &account.into());
+    }
+
+    /// Move the clock forward by `seconds`
+    pub async fn warp(&mut self, seconds: i64) {
+        let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += seconds;
+        self.context.set_sysvar(&clock);
+    }
+
+    pub async fn now(&mut self) -> i64 {
+        let clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp
+    }
+
+    pub async fn create_mint(&mut self) -> Pubkey {
+        let mint = Keypair::new();
+        let payer = self.payer();
+        self.create_mint_with(&mint, &payer.pubkey()).await;
+        mint.pubkey()
+    }
+
+    /// Create a 6-decimal mint at `mint` controlled by `authority`
+    pub async fn create_mint_with(&mut self, mint: &Keypair, authority: &Pubkey) {
+        let payer = self.payer();
+        let rent = Rent::default().minimum_balance(spl_token::state::Mint::LEN);
+        self.process(
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &mint.pubkey(),
+                    rent,
+                    spl_token::state::Mint::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_mint2(&spl_token::ID, &mint.pubkey(), authority, None, 6).unwrap(),
+            ],
+            &[mint],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Create a non-associated token account
+    pub async fn create_token_account(&mut self, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+        let account = Keypair::new();
+        let payer = self.payer();
+        let rent = Rent::default().minimum_balance(spl_token::state::Account::LEN);
+        self.process(
+            &[
+                system_instruction::create_account(
+                    &payer.pubkey(),
+                    &account.pubkey(),
+                    rent,
+                    spl_token::state::Account::LEN as u64,
+                    &spl_token::ID,
+                ),
+                spl_token::instruction::initialize_account3(&spl_token::ID, &account.pubkey(), mint, owner).unwrap(),
+            ],
+            &[&account],
+        )
+        .await
+        .unwrap();
+        account.pubkey()
+    }
+
+    /// Create `owner`'s associated token account for `mint` if missing
+    pub async fn create_ata(&mut self, mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+        let ata = anchor_spl::associated_token::get_associated_token_address(owner, mint);
+        if !self.account_exists(&ata).await {
+            let payer = self.payer();
+            self.process(
+                &[anchor_spl::associated_token::spl_associated_token_account::instruction::create_associated_token_account(
+                    &payer.pubkey(),
+                    owner,
+                    mint,
+                    &spl_token::ID,
+                )],
+                &[],
+            )
+            .await
+            .unwrap();
+        }
+        ata
+    }
+
+    pub async fn mint_to(&mut self, mint: &Pubkey, destination: &Pubkey, amount: u64) {
+        let payer = self.payer();
+        self.process(
+            &[spl_token::instruction::mint_to(&spl_token::ID, mint, destination, &payer.pubkey(), &[], amount).unwrap()],
+            &[],
+        )
+        .await
+        .unwrap();
+    }
+
+    /// Give `owner` an associated token account for `mint` holding `amount`
+    pub async fn fund_tokens(&mut self, mint: &Pubkey, owner: &Pubkey, amount: u64) -> Pubkey {
+        let ata = self.create_ata(mint, owner).await;
+        self.mint_to(mint, &ata, amount).await;
+        ata
+    }
+
+    pub async fn token_balance(&mut self, account: &Pubkey) -> u64 {
+        let account = self.context.banks_client.get_account(*account).await.unwrap().expect("token account not found");
+        spl_token::state::Account::unpack(&account.data).unwrap().amount
+    }
+
+    pub async fn mint_supply(&mut self, mint: &Pubkey) -> u64 {
+        let account = self.context.banks_client.get_account(*mint).await.unwrap().expect("mint not found");
+        spl_token::state::Mint::unpack(&account.data).unwrap().supply
+    }
+
+    /// Create the config with the payer as admin, unless it already exists
+    pub async fn init_config(&mut self) {
+        if self.account_exists(&pda::find_config_address().0).await {
+            return;
+        }
+        let payer = self.payer();
+        self.process(&[instructions::initialize_config(&payer.pubkey())], &[]).await.unwrap();
+    }
+
+    /// Keys for a new pool over two mints: vaults and LP mint are created with
+    /// the pool PDA as authority, but `initialize_pool` is not sent
+    pub async fn prepare_pool(&mut self, mint_x: Pubkey, mint_y: Pubkey) -> PoolKeys {
+        let (token_mint_a, token_mint_b) = pda::sort_mints(mint_x, mint_y);
+        let (pool, _) = pda::find_pool_address(&token_mint_a, &token_mint_b);
+
+        let lp_mint = Keypair::new();
+        self.create_mint_with(&lp_mint, &pool).await;
+
+        PoolKeys {
+            pool,
+            token_mint_a,
+            token_mint_b,
+            vault_a: self.create_token_account(&token_mint_a, &pool).await,
+            vault_b: self.create_token_account(&token_mint_b, &pool).await,
+            lp_mint: lp_mint.pubkey(),
+        }
+    }
+
+    /// Config plus an empty pool over two fresh mints
+    pub async fn empty_pool(&mut self) -> PoolKeys {
+        self.init_config().await;
+        let mint_x = self.create_mint().await;
+        let mint_y = self.create_mint().await;
+        let keys = self.prepare_pool(mint_x, mint_y).await;
+
+        let payer = self.payer();
+        self.process(&[instructions::initialize_pool(&payer.pubkey(), &keys)], &[]).await.unwrap();
+        keys
+    }
+
+    /// A pool seeded with `SEED_A`/`SEED_B` by a dedicated LP, who is returned
+    pub async fn seeded_pool(&mut self) -> (PoolKeys, Keypair) {
+        let keys = self.empty_pool().await;
+        let lp = self.new_user().await;
+        self.add_liquidity(&lp, &keys, SEED_A, SEED_B).await.unwrap();
+        (keys, lp)
+    }
+
+    /// Pool with native SOL on one side, seeded with 1 SOL against `SEED_B`
+    /// tokens by the returned LP
+    pub async fn native_pool(&mut self) -> (PoolKeys, Keypair) {
+        self.init_config().await;
+        let mint = self.create_mint().await;
+        let keys = self.prepare_pool(native_mint::ID, mint).await;
+        let payer = self.payer();
+        self.process(&[instructions::initialize_pool(&payer.pubkey(), &keys)], &[]).await.unwrap();
+
+        let lp = self.new_user().await;
+        self.fund_tokens(&mint, &lp.pubkey(), SEED_B).await;
+        self.create_ata(&keys.lp_mint, &lp.pubkey()).await;
+        let (amount_a_max, amount_b_max) = if keys.token_mint_a == native_mint::ID {
+            (SEED_A, SEED_B)
+        } else {
+            (SEED_B, SEED_A)
+        };
+        self.process(
+            &[instructions::add_liquidity_native(&lp.pubkey(), &keys, args::AddLiquidityNative {
+                amount_a_max,
+                amount_b_max,
+                amount_a_min: 0,
+                amount_b_min: 0,
+                min_lp_tokens: 0,
+                deadline: None,
+            })],
+            &[&lp],
+        )
+        .await
+        .unwrap();
+        (keys, lp)
+    }
+
+    /// Fund `user` with exactly the maximums and deposit them
+    pub async fn add_liquidity(&mut self, user: &Keypair, keys: &PoolKeys, amount_a: u64, amount_b: u64) -> TxResult {
+        self.fund_tokens(&keys.token_mint_a, &user.pubkey(), amount_a).await;
+        self.fund_tokens(&keys.token_mint_b, &user.pubkey(), amount_b).await;
+        self.process(
+            &[instructions::add_liquidity(&user.pubkey(), keys, args::AddLiquidity {
+                amount_a_max: amount_a,
+                amount_b_max: amount_b,
+                amount_a_min: 0,
+                amount_b_min: 0,
+                min_lp_tokens: 0,
+                deadline: None,
+            })],
+            &[user],
+        )
+        .await
+    }
+
+    pub async fn reserves(&mut self, keys: &PoolKeys) -> (u64, u64, u64) {
+        (
+            self.token_balance(&keys.vault_a).await,
+            self.token_balance(&keys.vault_b).await,
+            self.mint_supply(&keys.lp_mint).await,
+        )
+    }
+
+    pub async fn position_stats(&mut self, keys: &PoolKeys, owner: &Pubkey) -> PositionStats {
+        self.simulate(instructions::get_position_stats(keys, owner)).await
+    }
+}
@@ -0,0 +1,367 @@
+//! Compute units consumed by each instruction, checked against `compute_units.txt`.
+//!
+//! Only meaningful against the compiled program, so the test is skipped unless
+//! `SBF_OUT_DIR` points at a directory holding `amm.so`:
+//!
+//! ```bash
+//! cargo build-sbf --manifest-path programs/amm/Cargo.toml
+//! SBF_OUT_DIR=target/deploy cargo test --manifest-path crates/amm-program-tests/Cargo.toml --test compute_units
+//! ```
+//!
+//! Set `UPDATE_COMPUTE_UNITS=1` to rewrite the report with the measured values.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use amm_client::instructions::{self, args};
+use amm_client::pda;
+use amm_program_tests::{compiled_program_dir, TestEnv};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token::native_mint;
+use solana_sdk::signature::Signer;
+
+/// Allowed growth over the recorded value before the test fails
+const REGRESSION_THRESHOLD_PERCENT: u64 = 5;
+
+const REPORT_HEADER: &str = "\
+# Compute units per instruction, measured against the compiled program by
+# crates/amm-program-tests/tests/compute_units.rs. Token and system program
+# CPIs are included. Regenerate with UPDATE_COMPUTE_UNITS=1.
+";
+
+fn report_path() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("compute_units.txt")
+}
+
+fn read_report() -> BTreeMap<String, u64> {
+    let report = std::fs::read_to_string(report_path()).unwrap_or_default();
+    report
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+        .map(|line| {
+            let (name, units) = line.split_once(char::is_whitespace).expect("malformed report line");
+            (name.to_string(), units.trim().parse().expect("malformed compute units"))
+        })
+        .collect()
+}
+
+fn write_report(measured: &BTreeMap<String, u64>) {
+    let width = measured.keys().map(String::len).max().unwrap_or(0);
+    let mut report = REPORT_HEADER.to_string();
+    for (name, units) in measured {
+        report.push_str(&format!("{name:width$}  {units}\n"));
+    }
+    std::fs::write(report_path(), report).unwrap();
+}
+
+/// Run every instruction once against the compiled program
+async fn measure() -> BTreeMap<String, u64> {
+    let mut measured = BTreeMap::new();
+    let mut env = TestEnv::new().await;
+    let payer = env.payer();
+
+    // Admin and pool setup
+    measured.insert(
+        "initialize_config".into(),
+        env.process_metered(&[instructions::initialize_config(&payer.pubkey())], &[]).await,
+    );
+    let mint_x = env.create_mint().await;
+    let mint_y = env.create_mint().await;
+    let keys = env.prepare_pool(mint_x, mint_y).await;
+    measured.insert(
+        "initialize_pool".into(),
+        env.process_metered(&[instructions::initialize_pool(&payer.pubkey(), &keys)], &[]).await,
+    );
+    measured.insert(
+        "set_max_referral_bps".into(),
+        env.process_metered(&[instructions::set_max_referral_bps(&payer.pubkey(), 50)], &[]).await,
+    );
+
+    // Liquidity
+    let user = env.new_user().await;
+    env.fund_tokens(&keys.token_mint_a, &user.pubkey(), 10_000_000_000).await;
+    env.fund_tokens(&keys.token_mint_b, &user.pubkey(), 40_000_000_000).await;
+    let add = |amount_a_max, amount_b_max| {
+        instructions::add_liquidity(&user.pubkey(), &keys, args::AddLiquidity {
+            amount_a_max,
+            amount_b_max,
+            amount_a_min: 0,
+            amount_b_min: 0,
+            min_lp_tokens: 0,
+            deadline: None,
+        })
+    };
+    // The first deposit also creates the user's LP account and position
+    measured.insert(
+        "add_liquidity_first".into(),
+        env.process_metered(&[add(1_000_000_000, 4_000_000_000)], &[&user]).await,
+    );
+    measured.insert("add_liquidity".into(), env.process_metered(&[add(1_000_000, 4_000_000)], &[&user]).await);
+    measured.insert(
+        "add_liquidity_single".into(),
+        env.process_metered(
+            &[instructions::add_liquidity_single(&user.pubkey(), &keys, args::AddLiquiditySingle {
+                token_in: keys.token_mint_a,
+                amount_in: 1_000_000,
+                min_lp_tokens: 0,
+            })],
+            &[&user],
+        )
+        .await,
+    );
+    measured.insert(
+        "add_liquidity_exact_lp".into(),
+        env.process_metered(
+            &[instructions::add_liquidity_exact_lp(&user.pubkey(), &keys, args::AddLiquidityExactLp {
+                lp_out: 1_000_000,
+                max_amount_a: u64::MAX,
+                max_amount_b: u64::MAX,
+            })],
+            &[&user],
+        )
+        .await,
+    );
+    measured.insert(
+        "remove_liquidity".into(),
+        env.process_metered(
+            &[instructions::remove_liquidity(&user.pubkey(), &keys, args::RemoveLiquidity {
+                lp_amount: 1_000_000,
+                min_amount_a: 0,
+                min_amount_b: 0,
+                deadline: None,
+            })],
+            &[&user],
+        )
+        .await,
+    );
+    measured.insert(
+        "remove_liquidity_single".into(),
+        env.process_metered(
+            &[instructions::remove_liquidity_single(&user.pubkey(), &keys, args::RemoveLiquiditySingle {
+                lp_amount: 1_000_000,
+                token_out: keys.token_mint_b,
+                min_amount_out: 0,
+            })],
+            &[&user],
+        )
+        .await,
+    );
+    measured.insert(
+        "remove_liquidity_exact_out".into(),
+        env.process_metered(
+            &[instructions::remove_liquidity_exact_out(&user.pubkey(), &keys, args::RemoveLiquidityExactOut {
+                amount_a: 1_000_000,
+                amount_b: 1_000_000,
+                max_lp_in: u64::MAX,
+            })],
+            &[&user],
+        )
+        .await,
+    );
+    measured.insert(
+        "get_position_stats".into(),
+        env.process_metered(&[instructions::get_position_stats(&keys, &user.pubkey())], &[]).await,
+    );
+
+    // Swaps
+    let swap = |amount_in, referral_bps, referrer_token| {
+        instructions::swap(&user.pubkey(), &keys, &keys.token_mint_a, referrer_token, args::Swap {
+            amount_in,
+            minimum_amount_out: 0,
+            referral_bps,
+            deadline: None,
+        })
+    };
+    measured.insert("swap".into(), env.process_metered(&[swap(1_000_000, 0, None)], &[&user]).await);
+    let referrer_token = env.create_ata(&keys.token_mint_a, &payer.pubkey()).await;
+    measured.insert(
+        "swap_with_referral".into(),
+        env.process_metered(&[swap(1_000_001, 50, Some(referrer_token))], &[&user]).await,
+    );
+
+    // Admin maintenance
+    let stray_mint = env.create_mint().await;
+    let stray = env.create_token_account(&stray_mint, &keys.pool).await;
+    env.mint_to(&stray_mint, &stray, 1).await;
+    let destination = env.create_ata(&stray_mint, &payer.pubkey()).await;
+    measured.insert(
+        "recover_tokens".into(),
+        env.process_metered(&[instructions::recover_tokens(&payer.pubkey(), &keys.pool, &stray, &destination, 1)], &[])
+            .await,
+    );
+    measured.insert(
+        "set_emergency_mode".into(),
+        env.process_metered(&[instructions::set_emergency_mode(&payer.pubkey(), &keys.pool, true)], &[]).await,
+    );
+    env.process(&[instructions::set_emergency_mode(&payer.pubkey(), &keys.pool, false)], &[]).await.unwrap();
+    env.downgrade_config().await;
+    env.downgrade_pool(&keys.pool).await;
+    measured.insert(
+        "migrate_config".into(),
+        env.process_metered(&[instructions::migrate_config(&payer.pubkey())], &[]).await,
+    );
+    measured.insert(
+        "migrate_pool".into(),
+        env.process_metered(&[instructions::migrate_pool(&payer.pubkey(), &keys.pool)], &[]).await,
+    );
+
+    // Farming
+    let farm = pda::find_farm_address(&keys.pool).0;
+    measured.insert(
+        "initialize_farm".into(),
+        env.process_metered(&[instructions::initialize_farm(&payer.pubkey(), &keys)], &[]).await,
+    );
+    let reward_mint = env.create_mint().await;
+    measured.insert(
+        "add_reward_stream".into(),
+        env.process_metered(&[instructions::add_reward_stream(&payer.pubkey(), &farm, &reward_mint, payer.pubkey())], &[])
+            .await,
+    );
+    env.fund_tokens(&reward_mint, &payer.pubkey(), 1_000_000).await;
+    measured.insert(
+        "fund_reward_stream".into(),
+        env.process_metered(
+            &[instructions::fund_reward_stream(&payer.pubkey(), &farm, &reward_mint, args::FundRewardStream {
+                stream_index: 0,
+                amount: 1_000_000,
+                duration: 1_000,
+            })],
+            &[],
+        )
+        .await,
+    );
+    measured.insert(
+        "stake_lp".into(),
+        env.process_metered(&[instructions::stake_lp(&user.pubkey(), &keys, 1_000_000)], &[&user]).await,
+    );
+    env.warp(100).await;
+    measured.insert(
+        "claim_rewards".into(),
+        env.process_metered(&[instructions::claim_rewards(&user.pubkey(), &farm, &reward_mint, 0)], &[&user]).await,
+    );
+    env.warp(100).await;
+    measured.insert(
+        "claim_all_rewards".into(),
+        env.process_metered(&[instructions::claim_all_rewards(&user.pubkey(), &farm, &[reward_mint])], &[&user]).await,
+    );
+    measured.insert(
+        "unstake_lp".into(),
+        env.process_metered(&[instructions::unstake_lp(&user.pubkey(), &keys, 500_000)], &[&user]).await,
+    );
+    measured.insert(
+        "emergency_unstake_lp".into(),
+        env.process_metered(&[instructions::emergency_unstake_lp(&user.pubkey(), &keys)], &[&user]).await,
+    );
+
+    // Closing requires a drained pool
+    let user_lp = get_associated_token_address(&user.pubkey(), &keys.lp_mint);
+    let lp_amount = env.token_balance(&user_lp).await;
+    env.process(
+        &[instructions::remove_liquidity(&user.pubkey(), &keys, args::RemoveLiquidity {
+            lp_amount,
+            min_amount_a: 0,
+            min_amount_b: 0,
+            deadline: None,
+        })],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    let destination_a = env.create_ata(&keys.token_mint_a, &payer.pubkey()).await;
+    let destination_b = env.create_ata(&keys.token_mint_b, &payer.pubkey()).await;
+    let close = instructions::close_pool(&payer.pubkey(), &keys, &destination_a, &destination_b, &payer.pubkey());
+    measured.insert("close_pool".into(), env.process_metered(&[close], &[]).await);
+
+    // Native SOL pool
+    let (native_keys, lp) = env.native_pool().await;
+    let token_mint = native_keys.non_native_mint();
+    let (amount_a_max, amount_b_max) = if native_keys.token_mint_a == native_mint::ID {
+        (1_000_000, 4_000_000)
+    } else {
+        (4_000_000, 1_000_000)
+    };
+    env.fund_tokens(&token_mint, &lp.pubkey(), 4_000_000).await;
+    measured.insert(
+        "add_liquidity_native".into(),
+        env.process_metered(
+            &[instructions::add_liquidity_native(&lp.pubkey(), &native_keys, args::AddLiquidityNative {
+                amount_a_max,
+                amount_b_max,
+                amount_a_min: 0,
+                amount_b_min: 0,
+                min_lp_tokens: 0,
+                deadline: None,
+            })],
+            &[&lp],
+        )
+        .await,
+    );
+    measured.insert(
+        "remove_liquidity_native".into(),
+        env.process_metered(
+            &[instructions::remove_liquidity_native(&lp.pubkey(), &native_keys, args::RemoveLiquidityNative {
+                lp_amount: 1_000_000,
+                min_amount_a: 0,
+                min_amount_b: 0,
+                deadline: None,
+            })],
+            &[&lp],
+        )
+        .await,
+    );
+    measured.insert(
+        "swap_native".into(),
+        env.process_metered(
+            &[instructions::swap_native(&lp.pubkey(), &native_keys, &native_mint::ID, args::SwapNative {
+                amount_in: 1_000_000,
+                minimum_amount_out: 0,
+                deadline: None,
+            })],
+            &[&lp],
+        )
+        .await,
+    );
+
+    measured
+}
+
+#[tokio::test]
+async fn compute_units_within_budget() {
+    if compiled_program_dir().is_none() {
+        // CI builds amm.so first, so a missing build there is a broken pipeline
+        assert!(std::env::var("CI").is_err(), "amm.so not found: CI must run cargo build-sbf and set SBF_OUT_DIR");
+        eprintln!("skipping compute unit benchmark: set SBF_OUT_DIR to the directory holding amm.so");
+        return;
+    }
+
+    let measured = measure().await;
+    if std::env::var("UPDATE_COMPUTE_UNITS").is_ok() {
+        write_report(&measured);
+        return;
+    }
+
+    let recorded = read_report();
+    let mut regressions = Vec::new();
+    for (name, &units) in &measured {
+        match recorded.get(name) {
+            Some(&baseline) => {
+                let limit = baseline + baseline * REGRESSION_THRESHOLD_PERCENT / 100;
+                eprintln!("{name:28} {units:>8} (recorded {baseline})");
+                if units > limit {
+                    regressions.push(format!("{name}: {units} CU, recorded {baseline} CU"));
+                }
+            }
+            None => regressions.push(format!("{name}: {units} CU, not recorded")),
+        }
+    }
+    // Entries for instructions that are no longer measured are stale too
+    for name in recorded.keys().filter(|name| !measured.contains_key(*name)) {
+        regressions.push(format!("{name}: recorded but not measured"));
+    }
+    assert!(
+        regressions.is_empty(),
+        "compute units differ from compute_units.txt by more than {REGRESSION_THRESHOLD_PERCENT}% growth \
+         or a missing entry; rerun with UPDATE_COMPUTE_UNITS=1 after an intentional change:\n{}",
+        regressions.join("\n")
+    );
+}
@@ -0,0 +1,247 @@
+use amm::error::AmmError;
+use amm::state::{Farm, UserStake, MAX_REWARD_STREAMS};
+use amm_client::instructions::{self, args};
+use amm_client::{pda, PoolKeys};
+use amm_program_tests::{assert_amm_error, TestEnv};
+use anchor_lang::solana_program::pubkey::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use solana_sdk::signature::{Keypair, Signer};
+
+const REWARDS: u64 = 1_000_000;
+const DURATION: i64 = 1_000;
+
+struct FarmSetup {
+    keys: PoolKeys,
+    farm: Pubkey,
+    lp: Keypair,
+    reward_mint: Pubkey,
+}
+
+/// Seeded pool with a farm whose first stream, funded by the admin, emits
+/// `REWARDS` over `DURATION` seconds
+async fn funded_farm(env: &mut TestEnv) -> FarmSetup {
+    let (keys, lp) = env.seeded_pool().await;
+    let payer = env.payer();
+    let farm = pda::find_farm_address(&keys.pool).0;
+    env.process(&[instructions::initialize_farm(&payer.pubkey(), &keys)], &[]).await.unwrap();
+
+    let reward_mint = env.create_mint().await;
+    env.process(&[instructions::add_reward_stream(&payer.pubkey(), &farm, &reward_mint, payer.pubkey())], &[])
+        .await
+        .unwrap();
+    env.fund_tokens(&reward_mint, &payer.pubkey(), REWARDS).await;
+    env.process(
+        &[instructions::fund_reward_stream(&payer.pubkey(), &farm, &reward_mint, args::FundRewardStream {
+            stream_index: 0,
+            amount: REWARDS,
+            duration: DURATION,
+        })],
+        &[],
+    )
+    .await
+    .unwrap();
+
+    FarmSetup {
+        keys,
+        farm,
+        lp,
+        reward_mint,
+    }
+}
+
+#[tokio::test]
+async fn farm_setup_errors() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.seeded_pool().await;
+    let payer = env.payer();
+    let farm = pda::find_farm_address(&keys.pool).0;
+
+    let result = env.process(&[instructions::initialize_farm(&lp.pubkey(), &keys)], &[&lp]).await;
+    assert_amm_error(result, AmmError::Unauthorized);
+
+    let mut wrong_lp_mint = keys;
+    wrong_lp_mint.lp_mint = keys.token_mint_a;
+    let result = env.process(&[instructions::initialize_farm(&payer.pubkey(), &wrong_lp_mint)], &[]).await;
+    assert_amm_error(result, AmmError::InvalidLpMint);
+
+    env.process(&[instructions::initialize_farm(&payer.pubkey(), &keys)], &[]).await.unwrap();
+
+    let reward_mint = env.create_mint().await;
+    let result = env
+        .process(&[instructions::add_reward_stream(&lp.pubkey(), &farm, &reward_mint, lp.pubkey())], &[&lp])
+        .await;
+    assert_amm_error(result, AmmError::Unauthorized);
+
+    for _ in 0..MAX_REWARD_STREAMS {
+        let reward_mint = env.create_mint().await;
+        env.process(&[instructions::add_reward_stream(&payer.pubkey(), &farm, &reward_mint, lp.pubkey())], &[])
+            .await
+            .unwrap();
+    }
+    let result = env
+        .process(&[instructions::add_reward_stream(&payer.pubkey(), &farm, &reward_mint, lp.pubkey())], &[])
+        .await;
+    assert_amm_error(result, AmmError::TooManyRewardStreams);
+
+    // Only the stream's funder can fund it
+    let farm_state: Farm = env.get_account(&farm).await;
+    let first_mint = farm_state.reward_streams[0].reward_mint;
+    env.fund_tokens(&first_mint, &payer.pubkey(), REWARDS).await;
+    env.create_ata(&first_mint, &lp.pubkey()).await;
+    let fund = |funder: &Pubkey, stream_index, duration| {
+        instructions::fund_reward_stream(funder, &farm, &first_mint, args::FundRewardStream {
+            stream_index,
+            amount: REWARDS,
+            duration,
+        })
+    };
+
+    let result = env.process(&[fund(&payer.pubkey(), 0, DURATION)], &[]).await;
+    assert_amm_error(result, AmmError::Unauthorized);
+
+    let result = env.process(&[fund(&lp.pubkey(), 0, 0)], &[&lp]).await;
+    assert_amm_error(result, AmmError::InvalidRewardDuration);
+
+    let result = env.process(&[fund(&lp.pubkey(), MAX_REWARD_STREAMS as u8, DURATION)], &[&lp]).await;
+    assert_amm_error(result, AmmError::InvalidRewardStream);
+
+    // Vault of another stream
+    let result = env.process(&[fund(&lp.pubkey(), 1, DURATION)], &[&lp]).await;
+    assert_amm_error(result, AmmError::InvalidVault);
+}
+
+#[tokio::test]
+async fn stake_earn_and_claim() {
+    let mut env = TestEnv::new().await;
+    let FarmSetup {
+        keys,
+        farm,
+        lp,
+        reward_mint,
+    } = funded_farm(&mut env).await;
+
+    let result = env.process(&[instructions::stake_lp(&lp.pubkey(), &keys, 0)], &[&lp]).await;
+    assert_amm_error(result, AmmError::ZeroLiquidity);
+
+    env.process(&[instructions::stake_lp(&lp.pubkey(), &keys, 1_000_000_000)], &[&lp]).await.unwrap();
+    let stake: UserStake = env.get_account(&pda::find_user_stake_address(&farm, &lp.pubkey()).0).await;
+    assert_eq!(stake.amount, 1_000_000_000);
+    let farm_state: Farm = env.get_account(&farm).await;
+    assert_eq!(farm_state.total_staked, 1_000_000_000);
+
+    // Halfway through the period the sole staker has earned about half
+    env.warp(DURATION / 2).await;
+    env.process(&[instructions::claim_rewards(&lp.pubkey(), &farm, &reward_mint, 0)], &[&lp]).await.unwrap();
+    let user_reward = get_associated_token_address(&lp.pubkey(), &reward_mint);
+    let claimed = env.token_balance(&user_reward).await;
+    assert!((REWARDS / 2 - 1_000..=REWARDS / 2 + 1_000).contains(&claimed), "claimed {claimed}");
+
+    let result = env.process(&[instructions::claim_rewards(&lp.pubkey(), &farm, &reward_mint, 1)], &[&lp]).await;
+    assert_amm_error(result, AmmError::InvalidRewardStream);
+
+    // Right vault, wrong mint
+    let reward_vault = pda::find_farm_reward_vault_address(&farm, &reward_mint).0;
+    let other_mint = env.create_mint().await;
+    let mut wrong_mint = instructions::claim_rewards(&lp.pubkey(), &farm, &other_mint, 0);
+    wrong_mint.accounts[4].pubkey = reward_vault;
+    let result = env.process(&[wrong_mint], &[&lp]).await;
+    assert_amm_error(result, AmmError::InvalidMint);
+
+    // Right mint, wrong vault
+    let mut wrong_vault = instructions::claim_rewards(&lp.pubkey(), &farm, &reward_mint, 0);
+    wrong_vault.accounts[4].pubkey = user_reward;
+    let result = env.process(&[wrong_vault], &[&lp]).await;
+    assert_amm_error(result, AmmError::InvalidVault);
+
+    // After the period ends everything has been emitted
+    env.warp(DURATION).await;
+    let result = env.process(&[instructions::unstake_lp(&lp.pubkey(), &keys, 1_000_000_001)], &[&lp]).await;
+    assert_amm_error(result, AmmError::InsufficientStake);
+    let result = env.process(&[instructions::unstake_lp(&lp.pubkey(), &keys, 0)], &[&lp]).await;
+    assert_amm_error(result, AmmError::ZeroLiquidity);
+
+    env.process(&[instructions::unstake_lp(&lp.pubkey(), &keys, 1_000_000_000)], &[&lp]).await.unwrap();
+    env.process(&[instructions::claim_all_rewards(&lp.pubkey(), &farm, &[reward_mint])], &[&lp]).await.unwrap();
+
+    let total = env.token_balance(&user_reward).await;
+    assert!((REWARDS - 1_000..=REWARDS).contains(&total), "total {total}");
+    let user_lp = get_associated_token_address(&lp.pubkey(), &keys.lp_mint);
+    assert_eq!(env.token_balance(&user_lp).await, 2_000_000_000);
+}
+
+#[tokio::test]
+async fn claim_all_rewards_validates_accounts() {
+    let mut env = TestEnv::new().await;
+    let FarmSetup {
+        keys,
+        farm,
+        lp,
+        reward_mint,
+    } = funded_farm(&mut env).await;
+    env.process(&[instructions::stake_lp(&lp.pubkey(), &keys, 1_000_000_000)], &[&lp]).await.unwrap();
+    env.create_ata(&reward_mint, &lp.pubkey()).await;
+    env.warp(DURATION / 2).await;
+
+    // A pair is required for every active stream
+    let result = env.process(&[instructions::claim_all_rewards(&lp.pubkey(), &farm, &[])], &[&lp]).await;
+    assert_amm_error(result, AmmError::InvalidRewardStream);
+
+    // Reward paid into an account of another mint
+    let mut wrong_mint = instructions::claim_all_rewards(&lp.pubkey(), &farm, &[reward_mint]);
+    wrong_mint.accounts[5].pubkey = get_associated_token_address(&lp.pubkey(), &keys.lp_mint);
+    let result = env.process(&[wrong_mint], &[&lp]).await;
+    assert_amm_error(result, AmmError::InvalidMint);
+
+    // Reward paid into somebody else's account
+    let payer = env.payer();
+    let mut wrong_owner = instructions::claim_all_rewards(&lp.pubkey(), &farm, &[reward_mint]);
+    wrong_owner.accounts[5].pubkey = get_associated_token_address(&payer.pubkey(), &reward_mint);
+    let result = env.process(&[wrong_owner], &[&lp]).await;
+    assert_amm_error(result, AmmError::Unauthorized);
+
+    // Vault of the wrong stream
+    let mut wrong_vault = instructions::claim_all_rewards(&lp.pubkey(), &farm, &[reward_mint]);
+    wrong_vault.accounts[4].pubkey = get_associated_token_address(&lp.pubkey(), &reward_mint);
+    let result = env.process(&[wrong_vault], &[&lp]).await;
+    assert_amm_error(result, AmmError::InvalidVault);
+}
+
+#[tokio::test]
+async fn emergency_unstake_skips_overflowing_rewards() {
+    let mut env = TestEnv::new().await;
+    let FarmSetup {
+        keys,
+        farm,
+        lp,
+        reward_mint,
+    } = funded_farm(&mut env).await;
+
+    env.process(&[instructions::stake_lp(&lp.pubkey(), &keys, 1_000_000_000)], &[&lp]).await.unwrap();
+    env.warp(DURATION / 2).await;
+
+    // An accumulator this large no longer fits `amount * acc_reward_per_share`
+    let mut farm_state: Farm = env.get_account(&farm).await;
+    farm_state.reward_streams[0].acc_reward_per_share = u128::MAX / 2;
+    env.set_account(&farm, &farm_state).await;
+
+    let result = env.process(&[instructions::unstake_lp(&lp.pubkey(), &keys, 1_000_000_000)], &[&lp]).await;
+    assert_amm_error(result, AmmError::MathOverflow);
+    let result = env.process(&[instructions::claim_rewards(&lp.pubkey(), &farm, &reward_mint, 0)], &[&lp]).await;
+    assert_amm_error(result, AmmError::MathOverflow);
+
+    env.process(&[instructions::emergency_unstake_lp(&lp.pubkey(), &keys)], &[&lp]).await.unwrap();
+
+    let user_lp = get_associated_token_address(&lp.pubkey(), &keys.lp_mint);
+    assert_eq!(env.token_balance(&user_lp).await, 2_000_000_000);
+    let stake: UserStake = env.get_account(&pda::find_user_stake_address(&farm, &lp.pubkey()).0).await;
+    assert_eq!(stake.amount, 0);
+    assert_eq!(stake.pending_rewards, [0; MAX_REWARD_STREAMS]);
+    let farm_state: Farm = env.get_account(&farm).await;
+    assert_eq!(farm_state.total_staked, 0);
+    let result = env.process(&[instructions::emergency_unstake_lp(&lp.pubkey(), &keys)], &[&lp]).await;
+    assert_amm_error(result, AmmError::ZeroLiquidity);
+
+    // The forfeited rewards were never paid out
+    let reward_vault = pda::find_farm_reward_vault_address(&farm, &reward_mint).0;
+    assert_eq!(env.token_balance(&reward_vault).await, REWARDS);
+}
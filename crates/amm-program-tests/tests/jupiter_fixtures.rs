@@ -0,0 +1,48 @@
+//! Snapshots of a seeded pool for the `amm-jupiter` tests.
+//!
+//! The pool and its vaults are written by the program in the bank, then saved
+//! in the format of `solana account <ADDRESS> --output json`. The test is
+//! skipped unless `UPDATE_JUPITER_FIXTURES=1`:
+//!
+//! ```bash
+//! UPDATE_JUPITER_FIXTURES=1 cargo test --manifest-path crates/amm-program-tests/Cargo.toml --test jupiter_fixtures
+//! ```
+
+use std::path::Path;
+
+use amm_program_tests::TestEnv;
+use base64::Engine;
+use solana_sdk::pubkey::Pubkey;
+
+async fn write_snapshot(env: &mut TestEnv, address: &Pubkey, name: &str) {
+    let account = env.context.banks_client.get_account(*address).await.unwrap().expect("account not found");
+    let snapshot = serde_json::json!({
+        "account": {
+            "data": [base64::engine::general_purpose::STANDARD.encode(&account.data), "base64"],
+            "executable": account.executable,
+            "lamports": account.lamports,
+            "owner": account.owner.to_string(),
+            "rentEpoch": account.rent_epoch,
+            "space": account.data.len(),
+        },
+        "pubkey": address.to_string(),
+    });
+
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("../amm-jupiter/tests/fixtures/{name}.json"));
+    std::fs::write(path, serde_json::to_string_pretty(&snapshot).unwrap() + "\n").unwrap();
+}
+
+#[tokio::test]
+async fn write_jupiter_fixtures() {
+    if std::env::var("UPDATE_JUPITER_FIXTURES").is_err() {
+        eprintln!("skipping Jupiter fixtures: set UPDATE_JUPITER_FIXTURES=1 to rewrite them");
+        return;
+    }
+
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+
+    write_snapshot(&mut env, &keys.pool, "pool").await;
+    write_snapshot(&mut env, &keys.vault_a, "vault_a").await;
+    write_snapshot(&mut env, &keys.vault_b, "vault_b").await;
+}
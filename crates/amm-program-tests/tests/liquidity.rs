@@ -0,0 +1,567 @@
+use amm::error::AmmError;
+use amm::math::get_lp_for_exact_withdrawal;
+use amm::state::{LpPosition, Pool};
+use amm_client::instructions::{self, args};
+use amm_client::{pda, PoolKeys};
+use amm_program_tests::{assert_amm_error, TestEnv, SEED_A, SEED_B};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token::{self, native_mint};
+use solana_sdk::signature::{Keypair, Signer};
+
+fn remove_args(lp_amount: u64) -> args::RemoveLiquidity {
+    args::RemoveLiquidity {
+        lp_amount,
+        min_amount_a: 0,
+        min_amount_b: 0,
+        deadline: None,
+    }
+}
+
+async fn lp_balance(env: &mut TestEnv, keys: &PoolKeys, owner: &Keypair) -> u64 {
+    env.token_balance(&get_associated_token_address(&owner.pubkey(), &keys.lp_mint)).await
+}
+
+#[tokio::test]
+async fn first_deposit_mints_geometric_mean() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.seeded_pool().await;
+
+    assert_eq!(env.reserves(&keys).await, (SEED_A, SEED_B, 2_000_000_000));
+    assert_eq!(lp_balance(&mut env, &keys, &lp).await, 2_000_000_000);
+
+    let position: LpPosition = env.get_account(&pda::find_position_address(&keys.pool, &lp.pubkey()).0).await;
+    assert_eq!(position.owner, lp.pubkey());
+    assert_eq!(position.lp_amount, 2_000_000_000);
+    assert_eq!(position.entry_amount_a, SEED_A);
+    assert_eq!(position.entry_amount_b, SEED_B);
+}
+
+#[tokio::test]
+async fn add_liquidity_takes_the_reserve_ratio() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let user = env.new_user().await;
+
+    // Offers too much B; only 4x the A amount is taken
+    env.add_liquidity(&user, &keys, 1_000_000, 10_000_000).await.unwrap();
+
+    assert_eq!(env.reserves(&keys).await, (SEED_A + 1_000_000, SEED_B + 4_000_000, 2_002_000_000));
+    assert_eq!(lp_balance(&mut env, &keys, &user).await, 2_000_000);
+    let user_b = get_associated_token_address(&user.pubkey(), &keys.token_mint_b);
+    assert_eq!(env.token_balance(&user_b).await, 6_000_000);
+}
+
+#[tokio::test]
+async fn add_liquidity_errors() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let user = env.new_user().await;
+    env.fund_tokens(&keys.token_mint_a, &user.pubkey(), 1_000_000).await;
+    env.fund_tokens(&keys.token_mint_b, &user.pubkey(), 4_000_000).await;
+
+    let deposit = |amount_a_max, amount_b_max, amount_b_min, min_lp_tokens, deadline| {
+        instructions::add_liquidity(&user.pubkey(), &keys, args::AddLiquidity {
+            amount_a_max,
+            amount_b_max,
+            amount_a_min: 0,
+            amount_b_min,
+            min_lp_tokens,
+            deadline,
+        })
+    };
+
+    let result = env.process(&[deposit(0, 4_000_000, 0, 0, None)], &[&user]).await;
+    assert_amm_error(result, AmmError::ZeroLiquidity);
+
+    let result = env.process(&[deposit(1_000_000, 4_000_000, 0, 2_000_001, None)], &[&user]).await;
+    assert_amm_error(result, AmmError::SlippageExceeded);
+
+    let result = env.process(&[deposit(1_000_000, 4_000_000, 4_000_001, 0, None)], &[&user]).await;
+    assert_amm_error(result, AmmError::SlippageExceeded);
+
+    let expired = env.now().await - 1;
+    let result = env.process(&[deposit(1_000_000, 4_000_000, 0, 0, Some(expired))], &[&user]).await;
+    assert_amm_error(result, AmmError::DeadlineExceeded);
+}
+
+#[tokio::test]
+async fn add_liquidity_to_an_existing_lp_account() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let user = env.new_user().await;
+    env.fund_tokens(&keys.token_mint_a, &user.pubkey(), 1_000_000).await;
+    env.fund_tokens(&keys.token_mint_b, &user.pubkey(), 4_000_000).await;
+    let user_lp = env.create_token_account(&keys.lp_mint, &user.pubkey()).await;
+
+    let ix = instructions::add_liquidity_to(&user.pubkey(), &keys, &user_lp, args::AddLiquidity {
+        amount_a_max: 1_000_000,
+        amount_b_max: 4_000_000,
+        amount_a_min: 0,
+        amount_b_min: 0,
+        min_lp_tokens: 0,
+        deadline: None,
+    });
+    env.process(&[ix], &[&user]).await.unwrap();
+
+    // No associated LP account is created alongside it
+    assert_eq!(env.token_balance(&user_lp).await, 2_000_000);
+    let ata = get_associated_token_address(&user.pubkey(), &keys.lp_mint);
+    assert!(!env.account_exists(&ata).await);
+
+    // Someone else's LP account is refused
+    let other = env.new_user().await;
+    let other_lp = env.create_token_account(&keys.lp_mint, &other.pubkey()).await;
+    env.fund_tokens(&keys.token_mint_a, &user.pubkey(), 1_000_000).await;
+    env.fund_tokens(&keys.token_mint_b, &user.pubkey(), 4_000_000).await;
+    let ix = instructions::add_liquidity_to(&user.pubkey(), &keys, &other_lp, args::AddLiquidity {
+        amount_a_max: 1_000_000,
+        amount_b_max: 4_000_000,
+        amount_a_min: 0,
+        amount_b_min: 0,
+        min_lp_tokens: 0,
+        deadline: None,
+    });
+    assert!(env.process(&[ix], &[&user]).await.is_err());
+}
+
+#[tokio::test]
+async fn add_liquidity_single_sided() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let user = env.new_user().await;
+    env.fund_tokens(&keys.token_mint_a, &user.pubkey(), 10_000_000).await;
+    env.create_ata(&keys.lp_mint, &user.pubkey()).await;
+
+    let single = |token_in, amount_in, min_lp_tokens| {
+        instructions::add_liquidity_single(&user.pubkey(), &keys, args::AddLiquiditySingle {
+            token_in,
+            amount_in,
+            min_lp_tokens,
+        })
+    };
+
+    let result = env.process(&[single(keys.token_mint_a, 0, 0)], &[&user]).await;
+    assert_amm_error(result, AmmError::ZeroLiquidity);
+
+    let result = env.process(&[single(keys.token_mint_a, 10_000_000, 10_000_000)], &[&user]).await;
+    assert_amm_error(result, AmmError::SlippageExceeded);
+
+    // Depositing B from an A token account
+    let mut wrong = single(keys.token_mint_b, 10_000_000, 0);
+    wrong.accounts[5].pubkey = get_associated_token_address(&user.pubkey(), &keys.token_mint_a);
+    let result = env.process(&[wrong], &[&user]).await;
+    assert_amm_error(result, AmmError::InvalidMint);
+
+    let unrelated = env.create_mint().await;
+    env.create_ata(&unrelated, &user.pubkey()).await;
+    let result = env.process(&[single(unrelated, 10_000_000, 0)], &[&user]).await;
+    assert_amm_error(result, AmmError::InvalidMint);
+
+    env.process(&[single(keys.token_mint_a, 10_000_000, 0)], &[&user]).await.unwrap();
+    let (reserve_a, reserve_b, _) = env.reserves(&keys).await;
+    assert_eq!((reserve_a, reserve_b), (SEED_A + 10_000_000, SEED_B));
+    assert!(lp_balance(&mut env, &keys, &user).await > 0);
+}
+
+#[tokio::test]
+async fn single_sided_deposit_needs_a_price() {
+    let mut env = TestEnv::new().await;
+    let keys = env.empty_pool().await;
+    let user = env.new_user().await;
+    env.fund_tokens(&keys.token_mint_a, &user.pubkey(), 1_000).await;
+    env.fund_tokens(&keys.token_mint_b, &user.pubkey(), 1_000).await;
+    env.create_ata(&keys.lp_mint, &user.pubkey()).await;
+
+    let result = env
+        .process(
+            &[instructions::add_liquidity_single(&user.pubkey(), &keys, args::AddLiquiditySingle {
+                token_in: keys.token_mint_a,
+                amount_in: 1_000,
+                min_lp_tokens: 0,
+            })],
+            &[&user],
+        )
+        .await;
+    assert_amm_error(result, AmmError::PoolNotReady);
+
+    let result = env
+        .process(
+            &[instructions::add_liquidity_exact_lp(&user.pubkey(), &keys, args::AddLiquidityExactLp {
+                lp_out: 1_000,
+                max_amount_a: 1_000,
+                max_amount_b: 1_000,
+            })],
+            &[&user],
+        )
+        .await;
+    assert_amm_error(result, AmmError::PoolNotReady);
+}
+
+#[tokio::test]
+async fn add_liquidity_exact_lp() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let user = env.new_user().await;
+    env.fund_tokens(&keys.token_mint_a, &user.pubkey(), 1_000_000).await;
+    env.fund_tokens(&keys.token_mint_b, &user.pubkey(), 4_000_000).await;
+    env.create_ata(&keys.lp_mint, &user.pubkey()).await;
+
+    let exact = |lp_out, max_amount_a, max_amount_b| {
+        instructions::add_liquidity_exact_lp(&user.pubkey(), &keys, args::AddLiquidityExactLp {
+            lp_out,
+            max_amount_a,
+            max_amount_b,
+        })
+    };
+
+    let result = env.process(&[exact(0, 1_000_000, 4_000_000)], &[&user]).await;
+    assert_amm_error(result, AmmError::ZeroLiquidity);
+
+    let result = env.process(&[exact(2_000_000, 999_999, 4_000_000)], &[&user]).await;
+    assert_amm_error(result, AmmError::SlippageExceeded);
+
+    env.process(&[exact(2_000_000, 1_000_000, 4_000_000)], &[&user]).await.unwrap();
+    assert_eq!(lp_balance(&mut env, &keys, &user).await, 2_000_000);
+    assert_eq!(env.reserves(&keys).await, (SEED_A + 1_000_000, SEED_B + 4_000_000, 2_002_000_000));
+}
+
+#[tokio::test]
+async fn remove_liquidity_pays_out_pro_rata() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.seeded_pool().await;
+
+    let result = env.process(&[instructions::remove_liquidity(&lp.pubkey(), &keys, remove_args(0))], &[&lp]).await;
+    assert_amm_error(result, AmmError::ZeroLiquidity);
+
+    let mut too_greedy = remove_args(200_000_000);
+    too_greedy.min_amount_b = 400_000_001;
+    let result = env.process(&[instructions::remove_liquidity(&lp.pubkey(), &keys, too_greedy)], &[&lp]).await;
+    assert_amm_error(result, AmmError::SlippageExceeded);
+
+    let mut expired = remove_args(200_000_000);
+    expired.deadline = Some(env.now().await - 1);
+    let result = env.process(&[instructions::remove_liquidity(&lp.pubkey(), &keys, expired)], &[&lp]).await;
+    assert_amm_error(result, AmmError::DeadlineExceeded);
+
+    env.process(&[instructions::remove_liquidity(&lp.pubkey(), &keys, remove_args(200_000_000))], &[&lp])
+        .await
+        .unwrap();
+
+    assert_eq!(env.reserves(&keys).await, (SEED_A - 100_000_000, SEED_B - 400_000_000, 1_800_000_000));
+    let user_a = get_associated_token_address(&lp.pubkey(), &keys.token_mint_a);
+    let user_b = get_associated_token_address(&lp.pubkey(), &keys.token_mint_b);
+    assert_eq!(env.token_balance(&user_a).await, 100_000_000);
+    assert_eq!(env.token_balance(&user_b).await, 400_000_000);
+
+    let position: LpPosition = env.get_account(&pda::find_position_address(&keys.pool, &lp.pubkey()).0).await;
+    assert_eq!(position.lp_amount, 1_800_000_000);
+    assert_eq!(position.entry_amount_a, SEED_A - 100_000_000);
+}
+
+#[tokio::test]
+async fn remove_liquidity_without_a_position() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.seeded_pool().await;
+    let holder = env.new_user().await;
+    let holder_lp = env.create_ata(&keys.lp_mint, &holder.pubkey()).await;
+    let lp_account = get_associated_token_address(&lp.pubkey(), &keys.lp_mint);
+    env.process(
+        &[spl_token::instruction::transfer(&spl_token::ID, &lp_account, &holder_lp, &lp.pubkey(), &[], 200_000_000).unwrap()],
+        &[&lp],
+    )
+    .await
+    .unwrap();
+
+    // LP received by transfer is withdrawn without paying for a position
+    let remove = instructions::remove_liquidity(&holder.pubkey(), &keys, remove_args(200_000_000));
+    env.process(&[remove], &[&holder]).await.unwrap();
+    assert!(!env.account_exists(&pda::find_position_address(&keys.pool, &holder.pubkey()).0).await);
+    let holder_a = get_associated_token_address(&holder.pubkey(), &keys.token_mint_a);
+    assert_eq!(env.token_balance(&holder_a).await, 100_000_000);
+
+    // The depositor's position still counts the LP it minted
+    let position: LpPosition = env.get_account(&pda::find_position_address(&keys.pool, &lp.pubkey()).0).await;
+    assert_eq!(position.lp_amount, 2_000_000_000);
+}
+
+#[tokio::test]
+async fn remove_liquidity_to_existing_token_accounts() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.seeded_pool().await;
+    let user_a = env.create_token_account(&keys.token_mint_a, &lp.pubkey()).await;
+    let user_b = env.create_token_account(&keys.token_mint_b, &lp.pubkey()).await;
+    let ata_a = get_associated_token_address(&lp.pubkey(), &keys.token_mint_a);
+    let ata_a_balance = env.token_balance(&ata_a).await;
+
+    let ix = instructions::remove_liquidity_to(&lp.pubkey(), &keys, &user_a, &user_b, remove_args(200_000_000));
+    env.process(&[ix], &[&lp]).await.unwrap();
+
+    assert_eq!(env.token_balance(&user_a).await, 100_000_000);
+    assert_eq!(env.token_balance(&user_b).await, 400_000_000);
+    assert_eq!(env.token_balance(&ata_a).await, ata_a_balance);
+}
+
+#[tokio::test]
+async fn remove_liquidity_single_sided() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.seeded_pool().await;
+    env.create_ata(&keys.token_mint_b, &lp.pubkey()).await;
+
+    let single = |lp_amount, token_out, min_amount_out| {
+        instructions::remove_liquidity_single(&lp.pubkey(), &keys, args::RemoveLiquiditySingle {
+            lp_amount,
+            token_out,
+            min_amount_out,
+        })
+    };
+
+    let result = env.process(&[single(0, keys.token_mint_b, 0)], &[&lp]).await;
+    assert_amm_error(result, AmmError::ZeroLiquidity);
+
+    let result = env.process(&[single(2_000_000_001, keys.token_mint_b, 0)], &[&lp]).await;
+    assert_amm_error(result, AmmError::InsufficientLiquidity);
+
+    // Withdrawing the whole pool leaves nothing to swap the other side against
+    let result = env.process(&[single(2_000_000_000, keys.token_mint_b, 0)], &[&lp]).await;
+    assert_amm_error(result, AmmError::InsufficientLiquidity);
+
+    let result = env.process(&[single(20_000_000, keys.token_mint_b, 80_000_000)], &[&lp]).await;
+    assert_amm_error(result, AmmError::SlippageExceeded);
+
+    let unrelated = env.create_mint().await;
+    env.create_ata(&unrelated, &lp.pubkey()).await;
+    let result = env.process(&[single(20_000_000, unrelated, 0)], &[&lp]).await;
+    assert_amm_error(result, AmmError::InvalidMint);
+
+    env.process(&[single(20_000_000, keys.token_mint_b, 0)], &[&lp]).await.unwrap();
+    let user_b = get_associated_token_address(&lp.pubkey(), &keys.token_mint_b);
+    let received = env.token_balance(&user_b).await;
+    // 1% of the pool in B is 40M; the A share is swapped back at a small loss
+    assert!((79_000_000..80_000_000).contains(&received), "received {received}");
+    assert_eq!(env.reserves(&keys).await.0, SEED_A);
+}
+
+#[tokio::test]
+async fn remove_liquidity_exact_out() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.seeded_pool().await;
+    env.create_ata(&keys.token_mint_a, &lp.pubkey()).await;
+    env.create_ata(&keys.token_mint_b, &lp.pubkey()).await;
+
+    let exact = |amount_a, amount_b, max_lp_in| {
+        instructions::remove_liquidity_exact_out(&lp.pubkey(), &keys, args::RemoveLiquidityExactOut {
+            amount_a,
+            amount_b,
+            max_lp_in,
+        })
+    };
+
+    let result = env.process(&[exact(0, 0, u64::MAX)], &[&lp]).await;
+    assert_amm_error(result, AmmError::ZeroLiquidity);
+
+    // Taking only A charges the swap fee on the imbalance
+    let pool = env.get_account::<Pool>(&keys.pool).await;
+    let lp_in = get_lp_for_exact_withdrawal(1_000_000, 0, SEED_A, SEED_B, 2_000_000_000, pool.fee_bps).unwrap();
+    assert!(lp_in > 1_000_000);
+
+    let result = env.process(&[exact(1_000_000, 0, lp_in - 1)], &[&lp]).await;
+    assert_amm_error(result, AmmError::SlippageExceeded);
+
+    env.process(&[exact(1_000_000, 0, lp_in)], &[&lp]).await.unwrap();
+    let user_a = get_associated_token_address(&lp.pubkey(), &keys.token_mint_a);
+    let user_b = get_associated_token_address(&lp.pubkey(), &keys.token_mint_b);
+    assert_eq!(env.token_balance(&user_a).await, 1_000_000);
+    assert_eq!(env.token_balance(&user_b).await, 0);
+    assert_eq!(env.reserves(&keys).await, (SEED_A - 1_000_000, SEED_B, 2_000_000_000 - lp_in));
+}
+
+#[tokio::test]
+async fn every_liquidity_instruction_updates_the_position() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let user = env.new_user().await;
+    env.fund_tokens(&keys.token_mint_a, &user.pubkey(), 10_000_000).await;
+    env.fund_tokens(&keys.token_mint_b, &user.pubkey(), 10_000_000).await;
+    env.create_ata(&keys.lp_mint, &user.pubkey()).await;
+    let position_address = pda::find_position_address(&keys.pool, &user.pubkey()).0;
+
+    // A single-sided deposit records only the side paid in
+    env.process(
+        &[instructions::add_liquidity_single(&user.pubkey(), &keys, args::AddLiquiditySingle {
+            token_in: keys.token_mint_a,
+            amount_in: 1_000_000,
+            min_lp_tokens: 0,
+        })],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    let position: LpPosition = env.get_account(&position_address).await;
+    assert_eq!(position.owner, user.pubkey());
+    assert_eq!(position.lp_amount, lp_balance(&mut env, &keys, &user).await);
+    assert_eq!((position.entry_amount_a, position.entry_amount_b), (1_000_000, 0));
+    assert!(position.opened_at > 0);
+
+    env.process(
+        &[instructions::add_liquidity_exact_lp(&user.pubkey(), &keys, args::AddLiquidityExactLp {
+            lp_out: 2_000_000,
+            max_amount_a: 2_000_000,
+            max_amount_b: 5_000_000,
+        })],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    let position: LpPosition = env.get_account(&position_address).await;
+    assert_eq!(position.lp_amount, lp_balance(&mut env, &keys, &user).await);
+    assert!(position.entry_amount_a > 1_000_000 && position.entry_amount_b > 0);
+
+    env.process(
+        &[instructions::remove_liquidity_single(&user.pubkey(), &keys, args::RemoveLiquiditySingle {
+            lp_amount: 500_000,
+            token_out: keys.token_mint_b,
+            min_amount_out: 0,
+        })],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    let position: LpPosition = env.get_account(&position_address).await;
+    assert_eq!(position.lp_amount, lp_balance(&mut env, &keys, &user).await);
+
+    env.process(
+        &[instructions::remove_liquidity_exact_out(&user.pubkey(), &keys, args::RemoveLiquidityExactOut {
+            amount_a: 100_000,
+            amount_b: 0,
+            max_lp_in: u64::MAX,
+        })],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    let position: LpPosition = env.get_account(&position_address).await;
+    let remaining = lp_balance(&mut env, &keys, &user).await;
+    assert_eq!(position.lp_amount, remaining);
+
+    // Withdrawing the rest clears the position for a fresh start
+    env.process(&[instructions::remove_liquidity(&user.pubkey(), &keys, remove_args(remaining))], &[&user])
+        .await
+        .unwrap();
+    let position: LpPosition = env.get_account(&position_address).await;
+    assert_eq!(position.lp_amount, 0);
+    assert_eq!((position.entry_amount_a, position.entry_amount_b), (0, 0));
+    assert_eq!(position.entry_sqrt_k_per_lp, 0);
+    assert_eq!(position.opened_at, 0);
+}
+
+#[tokio::test]
+async fn position_stats_report_fees() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.seeded_pool().await;
+
+    let stats = env.position_stats(&keys, &lp.pubkey()).await;
+    assert_eq!(stats.lp_amount, 2_000_000_000);
+    assert_eq!((stats.amount_a, stats.amount_b), (SEED_A, SEED_B));
+    assert_eq!((stats.fees_a, stats.fees_b), (0, 0));
+
+    // Round-trip swaps grow k without moving the price much
+    let trader = env.new_user().await;
+    env.fund_tokens(&keys.token_mint_a, &trader.pubkey(), 100_000_000).await;
+    env.process(
+        &[instructions::swap(&trader.pubkey(), &keys, &keys.token_mint_a, None, args::Swap {
+            amount_in: 100_000_000,
+            minimum_amount_out: 0,
+            referral_bps: 0,
+            deadline: None,
+        })],
+        &[&trader],
+    )
+    .await
+    .unwrap();
+    let received_b = env.token_balance(&get_associated_token_address(&trader.pubkey(), &keys.token_mint_b)).await;
+    env.process(
+        &[instructions::swap(&trader.pubkey(), &keys, &keys.token_mint_b, None, args::Swap {
+            amount_in: received_b,
+            minimum_amount_out: 0,
+            referral_bps: 0,
+            deadline: None,
+        })],
+        &[&trader],
+    )
+    .await
+    .unwrap();
+
+    env.warp(3_600).await;
+    let stats = env.position_stats(&keys, &lp.pubkey()).await;
+    assert!(stats.fees_a > 0 || stats.fees_b > 0);
+    assert!(stats.position_value_b > 0);
+    assert!(stats.age_seconds >= 3_600);
+}
+
+#[tokio::test]
+async fn native_liquidity_round_trip() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.native_pool().await;
+    let sol_vault = if keys.token_mint_a == native_mint::ID { keys.vault_a } else { keys.vault_b };
+    assert_eq!(env.token_balance(&sol_vault).await, 1_000_000_000);
+    assert!(!env.account_exists(&pda::find_temp_wsol_address(&lp.pubkey()).0).await);
+
+    let lp_tokens = lp_balance(&mut env, &keys, &lp).await;
+    let before = env.lamports(&lp.pubkey()).await;
+    env.process(
+        &[instructions::remove_liquidity_native(&lp.pubkey(), &keys, args::RemoveLiquidityNative {
+            lp_amount: lp_tokens / 2,
+            min_amount_a: 0,
+            min_amount_b: 0,
+            deadline: None,
+        })],
+        &[&lp],
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(env.token_balance(&sol_vault).await, 500_000_000);
+    // Half a SOL comes back, less the transaction fee
+    assert!(env.lamports(&lp.pubkey()).await > before + 490_000_000);
+    let user_token = get_associated_token_address(&lp.pubkey(), &keys.non_native_mint());
+    assert_eq!(env.token_balance(&user_token).await, 2_000_000_000);
+
+    // Both native instructions keep the position in step with the LP
+    let position: LpPosition = env.get_account(&pda::find_position_address(&keys.pool, &lp.pubkey()).0).await;
+    assert_eq!(position.lp_amount, lp_tokens - lp_tokens / 2);
+}
+
+#[tokio::test]
+async fn native_instructions_reject_token_pools() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.seeded_pool().await;
+    env.fund_tokens(&keys.token_mint_a, &lp.pubkey(), 1_000).await;
+
+    let result = env
+        .process(
+            &[instructions::add_liquidity_native(&lp.pubkey(), &keys, args::AddLiquidityNative {
+                amount_a_max: 1_000,
+                amount_b_max: 4_000,
+                amount_a_min: 0,
+                amount_b_min: 0,
+                min_lp_tokens: 0,
+                deadline: None,
+            })],
+            &[&lp],
+        )
+        .await;
+    assert_amm_error(result, AmmError::InvalidMint);
+
+    let result = env
+        .process(
+            &[instructions::remove_liquidity_native(&lp.pubkey(), &keys, args::RemoveLiquidityNative {
+                lp_amount: 1_000,
+                min_amount_a: 0,
+                min_amount_b: 0,
+                deadline: None,
+            })],
+            &[&lp],
+        )
+        .await;
+    assert_amm_error(result, AmmError::InvalidMint);
+}
@@ -0,0 +1,313 @@
+use amm::error::AmmError;
+use amm::state::{Config, Pool, CONFIG_VERSION, POOL_VERSION};
+use amm_client::instructions::{self, args};
+use amm_client::{pda, PoolKeys};
+use amm_program_tests::{assert_amm_error, TestEnv};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::{Keypair, Signer};
+
+#[tokio::test]
+async fn initialize_config_sets_admin() {
+    let mut env = TestEnv::new().await;
+    env.init_config().await;
+
+    let config: Config = env.get_account(&pda::find_config_address().0).await;
+    assert_eq!(config.admin, env.payer().pubkey());
+    assert_eq!(config.max_referral_bps, 0);
+    assert_eq!(config.version, CONFIG_VERSION);
+}
+
+#[tokio::test]
+async fn initialize_pool_records_keys() {
+    let mut env = TestEnv::new().await;
+    let keys = env.empty_pool().await;
+
+    let pool = env.get_account::<Pool>(&keys.pool).await;
+    assert_eq!(pool.token_mint_a, keys.token_mint_a);
+    assert_eq!(pool.token_mint_b, keys.token_mint_b);
+    assert_eq!(pool.vault_a, keys.vault_a);
+    assert_eq!(pool.vault_b, keys.vault_b);
+    assert_eq!(pool.lp_mint, keys.lp_mint);
+    assert!(!pool.is_emergency_mode());
+    assert_eq!(pool.version, POOL_VERSION);
+}
+
+#[tokio::test]
+async fn initialize_pool_requires_admin() {
+    let mut env = TestEnv::new().await;
+    env.init_config().await;
+    let mint_x = env.create_mint().await;
+    let mint_y = env.create_mint().await;
+    let keys = env.prepare_pool(mint_x, mint_y).await;
+
+    let intruder = env.new_user().await;
+    let result = env.process(&[instructions::initialize_pool(&intruder.pubkey(), &keys)], &[&intruder]).await;
+    assert_amm_error(result, AmmError::Unauthorized);
+}
+
+#[tokio::test]
+async fn initialize_pool_rejects_identical_mints() {
+    let mut env = TestEnv::new().await;
+    env.init_config().await;
+    let mint = env.create_mint().await;
+    let keys = env.prepare_pool(mint, mint).await;
+
+    let payer = env.payer();
+    let result = env.process(&[instructions::initialize_pool(&payer.pubkey(), &keys)], &[]).await;
+    assert_amm_error(result, AmmError::IdenticalMints);
+}
+
+#[tokio::test]
+async fn initialize_pool_rejects_unsorted_mints() {
+    let mut env = TestEnv::new().await;
+    env.init_config().await;
+    let mint_x = env.create_mint().await;
+    let mint_y = env.create_mint().await;
+    let (low, high) = pda::sort_mints(mint_x, mint_y);
+
+    // Build the pool under the reversed order so only the order check can fail
+    let (pool, _) = pda::find_pool_address(&high, &low);
+    let lp_mint = Keypair::new();
+    env.create_mint_with(&lp_mint, &pool).await;
+    let keys = PoolKeys {
+        pool,
+        token_mint_a: high,
+        token_mint_b: low,
+        vault_a: env.create_token_account(&high, &pool).await,
+        vault_b: env.create_token_account(&low, &pool).await,
+        lp_mint: lp_mint.pubkey(),
+    };
+
+    let payer = env.payer();
+    let result = env.process(&[instructions::initialize_pool(&payer.pubkey(), &keys)], &[]).await;
+    assert_amm_error(result, AmmError::InvalidMintOrder);
+}
+
+#[tokio::test]
+async fn initialize_pool_validates_vaults_and_lp_mint() {
+    let mut env = TestEnv::new().await;
+    env.init_config().await;
+    let mint_x = env.create_mint().await;
+    let mint_y = env.create_mint().await;
+    let keys = env.prepare_pool(mint_x, mint_y).await;
+    let payer = env.payer();
+
+    // Vault owned by someone other than the pool
+    let mut bad = keys;
+    bad.vault_a = env.create_token_account(&keys.token_mint_a, &payer.pubkey()).await;
+    let result = env.process(&[instructions::initialize_pool(&payer.pubkey(), &bad)], &[]).await;
+    assert_amm_error(result, AmmError::InvalidVault);
+
+    // LP mint the pool cannot mint from
+    let mut bad = keys;
+    bad.lp_mint = env.create_mint().await;
+    let result = env.process(&[instructions::initialize_pool(&payer.pubkey(), &bad)], &[]).await;
+    assert_amm_error(result, AmmError::InvalidLpMint);
+}
+
+#[tokio::test]
+async fn close_pool_only_without_lp_supply() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.seeded_pool().await;
+    let payer = env.payer();
+    let recipient = env.new_user().await.pubkey();
+    let destination_a = env.create_token_account(&keys.token_mint_a, &payer.pubkey()).await;
+    let destination_b = env.create_token_account(&keys.token_mint_b, &payer.pubkey()).await;
+    let close_pool = |admin: &Pubkey| instructions::close_pool(admin, &keys, &destination_a, &destination_b, &recipient);
+
+    let result = env.process(&[close_pool(&payer.pubkey())], &[]).await;
+    assert_amm_error(result, AmmError::PoolNotEmpty);
+
+    let result = env.process(&[close_pool(&lp.pubkey())], &[&lp]).await;
+    assert_amm_error(result, AmmError::Unauthorized);
+
+    let (_, _, lp_supply) = env.reserves(&keys).await;
+    env.process(
+        &[instructions::remove_liquidity(&lp.pubkey(), &keys, args::RemoveLiquidity {
+            lp_amount: lp_supply,
+            min_amount_a: 0,
+            min_amount_b: 0,
+            deadline: None,
+        })],
+        &[&lp],
+    )
+    .await
+    .unwrap();
+
+    // Dust sent to a vault after the last withdrawal does not keep the pool open
+    env.mint_to(&keys.token_mint_a, &keys.vault_a, 7).await;
+
+    let mut wrong_mint = close_pool(&payer.pubkey());
+    wrong_mint.accounts[6].pubkey = destination_b;
+    let result = env.process(&[wrong_mint], &[]).await;
+    assert_amm_error(result, AmmError::InvalidMint);
+
+    // The swept balances can only go to the admin
+    let not_admin = env.create_token_account(&keys.token_mint_a, &lp.pubkey()).await;
+    let mut wrong_owner = close_pool(&payer.pubkey());
+    wrong_owner.accounts[6].pubkey = not_admin;
+    let result = env.process(&[wrong_owner], &[]).await;
+    assert_amm_error(result, AmmError::Unauthorized);
+
+    let before = env.lamports(&recipient).await;
+    env.process(&[close_pool(&payer.pubkey())], &[]).await.unwrap();
+
+    assert!(!env.account_exists(&keys.pool).await);
+    assert!(!env.account_exists(&keys.vault_a).await);
+    assert!(!env.account_exists(&keys.vault_b).await);
+    assert!(env.lamports(&recipient).await > before);
+    assert_eq!(env.token_balance(&destination_a).await, 7);
+}
+
+#[tokio::test]
+async fn set_max_referral_bps() {
+    let mut env = TestEnv::new().await;
+    env.init_config().await;
+    let payer = env.payer();
+
+    env.process(&[instructions::set_max_referral_bps(&payer.pubkey(), 50)], &[]).await.unwrap();
+    let config: Config = env.get_account(&pda::find_config_address().0).await;
+    assert_eq!(config.max_referral_bps, 50);
+
+    let result = env.process(&[instructions::set_max_referral_bps(&payer.pubkey(), 10_001)], &[]).await;
+    assert_amm_error(result, AmmError::ReferralFeeTooHigh);
+
+    let intruder = env.new_user().await;
+    let result = env.process(&[instructions::set_max_referral_bps(&intruder.pubkey(), 10)], &[&intruder]).await;
+    assert_amm_error(result, AmmError::Unauthorized);
+}
+
+#[tokio::test]
+async fn emergency_mode_allows_only_withdrawals() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.seeded_pool().await;
+    let payer = env.payer();
+
+    let result = env.process(&[instructions::set_emergency_mode(&lp.pubkey(), &keys.pool, true)], &[&lp]).await;
+    assert_amm_error(result, AmmError::Unauthorized);
+
+    env.process(&[instructions::set_emergency_mode(&payer.pubkey(), &keys.pool, true)], &[]).await.unwrap();
+    let pool = env.get_account::<Pool>(&keys.pool).await;
+    assert!(pool.is_emergency_mode());
+
+    let result = env.add_liquidity(&lp, &keys, 1_000, 4_000).await;
+    assert_amm_error(result, AmmError::EmergencyMode);
+
+    let result = env
+        .process(
+            &[instructions::swap(&lp.pubkey(), &keys, &keys.token_mint_a, None, args::Swap {
+                amount_in: 1_000,
+                minimum_amount_out: 0,
+                referral_bps: 0,
+                deadline: None,
+            })],
+            &[&lp],
+        )
+        .await;
+    assert_amm_error(result, AmmError::EmergencyMode);
+
+    // Proportional withdrawals stay open
+    env.process(
+        &[instructions::remove_liquidity(&lp.pubkey(), &keys, args::RemoveLiquidity {
+            lp_amount: 1_000,
+            min_amount_a: 0,
+            min_amount_b: 0,
+            deadline: None,
+        })],
+        &[&lp],
+    )
+    .await
+    .unwrap();
+
+    env.process(&[instructions::set_emergency_mode(&payer.pubkey(), &keys.pool, false)], &[]).await.unwrap();
+    env.add_liquidity(&lp, &keys, 1_000, 4_000).await.unwrap();
+}
+
+#[tokio::test]
+async fn recover_tokens_sent_to_the_pool() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let payer = env.payer();
+
+    // Tokens of an unrelated mint that ended up in an account owned by the pool
+    let stray_mint = env.create_mint().await;
+    let stray = env.create_token_account(&stray_mint, &keys.pool).await;
+    env.mint_to(&stray_mint, &stray, 500).await;
+    let destination = env.create_ata(&stray_mint, &payer.pubkey()).await;
+
+    let result = env.process(&[instructions::recover_tokens(&payer.pubkey(), &keys.pool, &stray, &destination, 0)], &[]).await;
+    assert_amm_error(result, AmmError::ZeroLiquidity);
+
+    let intruder = env.new_user().await;
+    let result = env
+        .process(
+            &[instructions::recover_tokens(&intruder.pubkey(), &keys.pool, &stray, &destination, 500)],
+            &[&intruder],
+        )
+        .await;
+    assert_amm_error(result, AmmError::Unauthorized);
+
+    let result = env
+        .process(&[instructions::recover_tokens(&payer.pubkey(), &keys.pool, &keys.vault_a, &destination, 1)], &[])
+        .await;
+    assert_amm_error(result, AmmError::CannotRecoverPoolTokens);
+
+    let wrong_destination = env.create_ata(&keys.token_mint_a, &payer.pubkey()).await;
+    let result = env
+        .process(&[instructions::recover_tokens(&payer.pubkey(), &keys.pool, &stray, &wrong_destination, 500)], &[])
+        .await;
+    assert_amm_error(result, AmmError::InvalidMint);
+
+    let not_pool_owned = env.create_token_account(&stray_mint, &payer.pubkey()).await;
+    let result = env
+        .process(&[instructions::recover_tokens(&payer.pubkey(), &keys.pool, &not_pool_owned, &destination, 1)], &[])
+        .await;
+    assert_amm_error(result, AmmError::InvalidVault);
+
+    env.process(&[instructions::recover_tokens(&payer.pubkey(), &keys.pool, &stray, &destination, 500)], &[])
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(&destination).await, 500);
+    assert_eq!(env.token_balance(&stray).await, 0);
+}
+
+#[tokio::test]
+async fn migrate_legacy_config_and_pool() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.seeded_pool().await;
+    let payer = env.payer();
+    let config_address = pda::find_config_address().0;
+
+    // Freshly created accounts are already current
+    let result = env.process(&[instructions::migrate_config(&payer.pubkey())], &[]).await;
+    assert_amm_error(result, AmmError::AlreadyMigrated);
+    let result = env.process(&[instructions::migrate_pool(&payer.pubkey(), &keys.pool)], &[]).await;
+    assert_amm_error(result, AmmError::AlreadyMigrated);
+
+    env.downgrade_config().await;
+    env.downgrade_pool(&keys.pool).await;
+
+    let result = env.process(&[instructions::migrate_config(&lp.pubkey())], &[&lp]).await;
+    assert_amm_error(result, AmmError::Unauthorized);
+    env.process(&[instructions::migrate_config(&payer.pubkey())], &[]).await.unwrap();
+    let config: Config = env.get_account(&config_address).await;
+    assert_eq!(config.admin, payer.pubkey());
+    assert_eq!(config.version, CONFIG_VERSION);
+
+    let result = env.process(&[instructions::migrate_pool(&lp.pubkey(), &keys.pool)], &[&lp]).await;
+    assert_amm_error(result, AmmError::Unauthorized);
+    env.process(&[instructions::migrate_pool(&payer.pubkey(), &keys.pool)], &[]).await.unwrap();
+    let pool = env.get_account::<Pool>(&keys.pool).await;
+    assert_eq!(PoolKeys::new(keys.pool, &pool), keys);
+    assert_eq!(pool.fee_bps, 30);
+    assert_eq!(pool.version, POOL_VERSION);
+    assert_eq!(pool.reserved, [0; 128]);
+
+    let account = env.context.banks_client.get_account(keys.pool).await.unwrap().unwrap();
+    assert_eq!(account.data.len(), Pool::LEN);
+    assert!(Rent::default().is_exempt(account.lamports, account.data.len()));
+
+    // The migrated pool trades as before
+    env.add_liquidity(&lp, &keys, 1_000_000, 4_000_000).await.unwrap();
+}
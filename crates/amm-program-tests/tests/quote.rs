@@ -0,0 +1,146 @@
+//! Client quotes against what the program actually does on the same reserves.
+
+use amm::state::Pool;
+use amm_client::instructions::{self, args};
+use amm_client::quote::{
+    quote_add_liquidity_exact_lp, quote_add_liquidity_single, quote_remove_liquidity_exact_out,
+    quote_remove_liquidity_single, quote_swap, PoolReserves,
+};
+use amm_client::PoolKeys;
+use amm_program_tests::TestEnv;
+use anchor_spl::associated_token::get_associated_token_address;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+async fn pool_state(env: &mut TestEnv, keys: &PoolKeys) -> (Pool, PoolReserves) {
+    let pool = env.get_account::<Pool>(&keys.pool).await;
+    let (reserve_a, reserve_b, lp_supply) = env.reserves(keys).await;
+    (pool, PoolReserves { reserve_a, reserve_b, lp_supply })
+}
+
+async fn balance(env: &mut TestEnv, owner: &Pubkey, mint: &Pubkey) -> u64 {
+    env.token_balance(&get_associated_token_address(owner, mint)).await
+}
+
+#[tokio::test]
+async fn single_sided_deposit_mints_the_quoted_lp() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let user = env.new_user().await;
+    env.fund_tokens(&keys.token_mint_b, &user.pubkey(), 37_000_003).await;
+    env.create_ata(&keys.lp_mint, &user.pubkey()).await;
+
+    let (pool, reserves) = pool_state(&mut env, &keys).await;
+    let lp_tokens = quote_add_liquidity_single(&pool, &reserves, &keys.token_mint_b, 37_000_003).unwrap();
+
+    env.process(
+        &[instructions::add_liquidity_single(&user.pubkey(), &keys, args::AddLiquiditySingle {
+            token_in: keys.token_mint_b,
+            amount_in: 37_000_003,
+            min_lp_tokens: lp_tokens,
+        })],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    assert_eq!(balance(&mut env, &user.pubkey(), &keys.lp_mint).await, lp_tokens);
+}
+
+#[tokio::test]
+async fn exact_lp_deposit_charges_the_quoted_amounts() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let user = env.new_user().await;
+    env.fund_tokens(&keys.token_mint_a, &user.pubkey(), 10_000_000).await;
+    env.fund_tokens(&keys.token_mint_b, &user.pubkey(), 10_000_000).await;
+    env.create_ata(&keys.lp_mint, &user.pubkey()).await;
+
+    let (pool, reserves) = pool_state(&mut env, &keys).await;
+    let (amount_a, amount_b) = quote_add_liquidity_exact_lp(&pool, &reserves, 1_234_567).unwrap();
+
+    env.process(
+        &[instructions::add_liquidity_exact_lp(&user.pubkey(), &keys, args::AddLiquidityExactLp {
+            lp_out: 1_234_567,
+            max_amount_a: amount_a,
+            max_amount_b: amount_b,
+        })],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    assert_eq!(balance(&mut env, &user.pubkey(), &keys.token_mint_a).await, 10_000_000 - amount_a);
+    assert_eq!(balance(&mut env, &user.pubkey(), &keys.token_mint_b).await, 10_000_000 - amount_b);
+    assert_eq!(balance(&mut env, &user.pubkey(), &keys.lp_mint).await, 1_234_567);
+}
+
+#[tokio::test]
+async fn single_sided_withdrawal_pays_the_quoted_amount() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.seeded_pool().await;
+    let before = balance(&mut env, &lp.pubkey(), &keys.token_mint_a).await;
+
+    let (pool, reserves) = pool_state(&mut env, &keys).await;
+    let amount_out = quote_remove_liquidity_single(&pool, &reserves, &keys.token_mint_a, 123_456_789).unwrap();
+
+    env.process(
+        &[instructions::remove_liquidity_single(&lp.pubkey(), &keys, args::RemoveLiquiditySingle {
+            lp_amount: 123_456_789,
+            token_out: keys.token_mint_a,
+            min_amount_out: amount_out,
+        })],
+        &[&lp],
+    )
+    .await
+    .unwrap();
+    assert_eq!(balance(&mut env, &lp.pubkey(), &keys.token_mint_a).await - before, amount_out);
+}
+
+#[tokio::test]
+async fn exact_out_withdrawal_burns_the_quoted_lp() {
+    let mut env = TestEnv::new().await;
+    let (keys, lp) = env.seeded_pool().await;
+    env.create_ata(&keys.token_mint_a, &lp.pubkey()).await;
+    env.create_ata(&keys.token_mint_b, &lp.pubkey()).await;
+    let before = balance(&mut env, &lp.pubkey(), &keys.lp_mint).await;
+
+    let (pool, reserves) = pool_state(&mut env, &keys).await;
+    let lp_in = quote_remove_liquidity_exact_out(&pool, &reserves, 3_000_001, 50_000_000).unwrap();
+
+    env.process(
+        &[instructions::remove_liquidity_exact_out(&lp.pubkey(), &keys, args::RemoveLiquidityExactOut {
+            amount_a: 3_000_001,
+            amount_b: 50_000_000,
+            max_lp_in: lp_in,
+        })],
+        &[&lp],
+    )
+    .await
+    .unwrap();
+    assert_eq!(before - balance(&mut env, &lp.pubkey(), &keys.lp_mint).await, lp_in);
+    assert_eq!(balance(&mut env, &lp.pubkey(), &keys.token_mint_a).await, 3_000_001);
+    assert_eq!(balance(&mut env, &lp.pubkey(), &keys.token_mint_b).await, 50_000_000);
+}
+
+#[tokio::test]
+async fn swap_pays_the_quoted_amount() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let user = env.new_user().await;
+    env.fund_tokens(&keys.token_mint_a, &user.pubkey(), 25_000_017).await;
+
+    let (pool, reserves) = pool_state(&mut env, &keys).await;
+    let quote = quote_swap(&pool, &reserves, &keys.token_mint_a, 25_000_017, 0).unwrap();
+
+    env.process(
+        &[instructions::swap(&user.pubkey(), &keys, &keys.token_mint_a, None, args::Swap {
+            amount_in: 25_000_017,
+            minimum_amount_out: quote.amount_out,
+            referral_bps: 0,
+            deadline: None,
+        })],
+        &[&user],
+    )
+    .await
+    .unwrap();
+    assert_eq!(balance(&mut env, &user.pubkey(), &keys.token_mint_b).await, quote.amount_out);
+}
@@ -0,0 +1,229 @@
+use amm::error::AmmError;
+use amm::math::get_amount_out;
+use amm::state::Pool;
+use amm_client::instructions::{self, args};
+use amm_client::{pda, PoolKeys};
+use amm_program_tests::{assert_amm_error, TestEnv, SEED_A, SEED_B};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token::native_mint;
+use solana_sdk::instruction::AccountMeta;
+use solana_sdk::signature::{Keypair, Signer};
+
+fn swap_args(amount_in: u64, minimum_amount_out: u64) -> args::Swap {
+    args::Swap {
+        amount_in,
+        minimum_amount_out,
+        referral_bps: 0,
+        deadline: None,
+    }
+}
+
+fn referral_args() -> args::Swap {
+    args::Swap {
+        referral_bps: 50,
+        ..swap_args(10_000_000, 0)
+    }
+}
+
+async fn trader(env: &mut TestEnv, keys: &PoolKeys) -> Keypair {
+    let trader = env.new_user().await;
+    env.fund_tokens(&keys.token_mint_a, &trader.pubkey(), 10_000_000).await;
+    trader
+}
+
+#[tokio::test]
+async fn swap_pays_the_constant_product_price() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let trader = trader(&mut env, &keys).await;
+    let pool = env.get_account::<Pool>(&keys.pool).await;
+    let expected = get_amount_out(10_000_000, SEED_A, SEED_B, pool.fee_bps).unwrap();
+
+    // The output account is created on the fly
+    env.process(
+        &[instructions::swap(&trader.pubkey(), &keys, &keys.token_mint_a, None, swap_args(10_000_000, expected))],
+        &[&trader],
+    )
+    .await
+    .unwrap();
+
+    let user_b = get_associated_token_address(&trader.pubkey(), &keys.token_mint_b);
+    assert_eq!(env.token_balance(&user_b).await, expected);
+    assert_eq!(env.reserves(&keys).await, (SEED_A + 10_000_000, SEED_B - expected, 2_000_000_000));
+}
+
+#[tokio::test]
+async fn swap_errors() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let trader = trader(&mut env, &keys).await;
+    let pool = env.get_account::<Pool>(&keys.pool).await;
+    let expected = get_amount_out(10_000_000, SEED_A, SEED_B, pool.fee_bps).unwrap();
+    let swap = |args| instructions::swap(&trader.pubkey(), &keys, &keys.token_mint_a, None, args);
+
+    let result = env.process(&[swap(swap_args(0, 0))], &[&trader]).await;
+    assert_amm_error(result, AmmError::ZeroLiquidity);
+
+    let result = env.process(&[swap(swap_args(10_000_000, expected + 1))], &[&trader]).await;
+    assert_amm_error(result, AmmError::SlippageExceeded);
+
+    let mut expired = swap_args(10_000_000, 0);
+    expired.deadline = Some(env.now().await - 1);
+    let result = env.process(&[swap(expired)], &[&trader]).await;
+    assert_amm_error(result, AmmError::DeadlineExceeded);
+
+    // Input and output vault are the same
+    let mut same_vault = swap(swap_args(10_000_000, 0));
+    same_vault.accounts[9].pubkey = keys.vault_a;
+    let result = env.process(&[same_vault], &[&trader]).await;
+    assert_amm_error(result, AmmError::InvalidVault);
+
+    // Output mint that is not part of the pool
+    let unrelated = env.create_mint().await;
+    let mut wrong_mint = swap(swap_args(10_000_000, 0));
+    wrong_mint.accounts[5].pubkey = unrelated;
+    wrong_mint.accounts[11].pubkey = get_associated_token_address(&trader.pubkey(), &unrelated);
+    let result = env.process(&[wrong_mint], &[&trader]).await;
+    assert_amm_error(result, AmmError::InvalidMint);
+}
+
+#[tokio::test]
+async fn swap_to_an_existing_token_account() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let trader = trader(&mut env, &keys).await;
+    let pool = env.get_account::<Pool>(&keys.pool).await;
+    let expected = get_amount_out(10_000_000, SEED_A, SEED_B, pool.fee_bps).unwrap();
+    let user_b = env.create_token_account(&keys.token_mint_b, &trader.pubkey()).await;
+    let swap_to = || {
+        let args = swap_args(10_000_000, expected);
+        instructions::swap_to(&trader.pubkey(), &keys, &keys.token_mint_a, &user_b, None, args)
+    };
+
+    // Exactly one of the existing account and the associated token account
+    let ata = get_associated_token_address(&trader.pubkey(), &keys.token_mint_b);
+    let mut both = swap_to();
+    both.accounts[11] = AccountMeta::new(ata, false);
+    let result = env.process(&[both], &[&trader]).await;
+    assert_amm_error(result, AmmError::InvalidOutputAccount);
+    let mut neither = swap_to();
+    neither.accounts[7].pubkey = amm::ID;
+    let result = env.process(&[neither], &[&trader]).await;
+    assert_amm_error(result, AmmError::InvalidOutputAccount);
+
+    env.process(&[swap_to()], &[&trader]).await.unwrap();
+    assert_eq!(env.token_balance(&user_b).await, expected);
+    assert!(!env.account_exists(&ata).await);
+}
+
+#[tokio::test]
+async fn swap_needs_reserves() {
+    let mut env = TestEnv::new().await;
+    let keys = env.empty_pool().await;
+    let trader = trader(&mut env, &keys).await;
+
+    let result = env
+        .process(
+            &[instructions::swap(&trader.pubkey(), &keys, &keys.token_mint_a, None, swap_args(1_000, 0))],
+            &[&trader],
+        )
+        .await;
+    assert_amm_error(result, AmmError::PoolNotReady);
+}
+
+#[tokio::test]
+async fn swap_pays_referral_within_the_cap() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let trader = trader(&mut env, &keys).await;
+    let referrer = env.new_user().await;
+    let referrer_token = env.create_ata(&keys.token_mint_a, &referrer.pubkey()).await;
+    let payer = env.payer();
+
+    let swap = |args| instructions::swap(&trader.pubkey(), &keys, &keys.token_mint_a, Some(referrer_token), args);
+
+    // The cap starts at zero
+    let result = env.process(&[swap(referral_args())], &[&trader]).await;
+    assert_amm_error(result, AmmError::ReferralFeeTooHigh);
+
+    env.process(&[instructions::set_max_referral_bps(&payer.pubkey(), 50)], &[]).await.unwrap();
+    env.process(&[swap(referral_args())], &[&trader]).await.unwrap();
+    assert_eq!(env.token_balance(&referrer_token).await, 50_000);
+    assert_eq!(env.reserves(&keys).await.0, SEED_A + 10_000_000 - 50_000);
+
+    // Referral paid in the wrong token
+    let wrong_token = env.create_ata(&keys.token_mint_b, &referrer.pubkey()).await;
+    let result = env
+        .process(
+            &[instructions::swap(&trader.pubkey(), &keys, &keys.token_mint_a, Some(wrong_token), referral_args())],
+            &[&trader],
+        )
+        .await;
+    assert_amm_error(result, AmmError::InvalidMint);
+}
+
+#[tokio::test]
+async fn swap_native_in_both_directions() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.native_pool().await;
+    let token_mint = keys.non_native_mint();
+    let trader = env.new_user().await;
+    let user_token = env.create_ata(&token_mint, &trader.pubkey()).await;
+
+    let native_args = |amount_in, minimum_amount_out| args::SwapNative {
+        amount_in,
+        minimum_amount_out,
+        deadline: None,
+    };
+
+    let result = env
+        .process(&[instructions::swap_native(&trader.pubkey(), &keys, &native_mint::ID, native_args(0, 0))], &[&trader])
+        .await;
+    assert_amm_error(result, AmmError::ZeroLiquidity);
+
+    let result = env
+        .process(
+            &[instructions::swap_native(&trader.pubkey(), &keys, &native_mint::ID, native_args(100_000_000, u64::MAX))],
+            &[&trader],
+        )
+        .await;
+    assert_amm_error(result, AmmError::SlippageExceeded);
+
+    // Lamports in, tokens out
+    env.process(
+        &[instructions::swap_native(&trader.pubkey(), &keys, &native_mint::ID, native_args(100_000_000, 0))],
+        &[&trader],
+    )
+    .await
+    .unwrap();
+    let tokens = env.token_balance(&user_token).await;
+    assert!(tokens > 0);
+    assert!(!env.account_exists(&pda::find_temp_wsol_address(&trader.pubkey()).0).await);
+
+    // Tokens in, lamports out
+    let before = env.lamports(&trader.pubkey()).await;
+    env.process(&[instructions::swap_native(&trader.pubkey(), &keys, &token_mint, native_args(tokens, 0))], &[&trader])
+        .await
+        .unwrap();
+    assert_eq!(env.token_balance(&user_token).await, 0);
+    assert!(env.lamports(&trader.pubkey()).await > before);
+}
+
+#[tokio::test]
+async fn swap_native_rejects_token_pools() {
+    let mut env = TestEnv::new().await;
+    let (keys, _) = env.seeded_pool().await;
+    let trader = trader(&mut env, &keys).await;
+
+    let result = env
+        .process(
+            &[instructions::swap_native(&trader.pubkey(), &keys, &keys.token_mint_a, args::SwapNative {
+                amount_in: 1_000,
+                minimum_amount_out: 0,
+                deadline: None,
+            })],
+            &[&trader],
+        )
+        .await;
+    assert_amm_error(result, AmmError::InvalidMint);
+}
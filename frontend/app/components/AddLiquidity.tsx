@@ -1,10 +1,10 @@
 "use client";
 
 import { useState } from "react";
-import { PublicKey, Transaction } from "@solana/web3.js";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
 import { useWallet, useConnection } from "@solana/wallet-adapter-react";
-import { getAssociatedTokenAddress, createAssociatedTokenAccountInstruction, TOKEN_PROGRAM_ID } from "@solana/spl-token";
-import { useAmmProgram, getPoolAddress } from "@/lib";
+import { getAssociatedTokenAddress, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { useAmmProgram, getPoolAddress, getPositionAddress, quoteDeposit, withSlippage, deadlineFromNow } from "@/lib";
 import { BN } from "@coral-xyz/anchor";
 
 interface AddLiquidityProps {
@@ -18,7 +18,7 @@ export function AddLiquidity({
   tokenMintB,
   poolAddress,
 }: AddLiquidityProps) {
-  const { publicKey, signTransaction } = useWallet();
+  const { publicKey } = useWallet();
   const { connection } = useConnection();
   const program = useAmmProgram();
 
@@ -83,48 +83,39 @@ export function AddLiquidity({
         mintBPubkey,
         publicKey
       );
-      const userLp = await getAssociatedTokenAddress(
+      // The program creates the LP token account if it does not exist yet
+      const userLpAta = await getAssociatedTokenAddress(
         poolAccount.lpMint,
         publicKey
       );
+      const [position] = getPositionAddress(poolPda, publicKey);
 
-      // Check if LP token account exists, if not create it
-      const lpAccountInfo = await connection.getAccountInfo(userLp);
-      if (!lpAccountInfo) {
-        console.log("Creating LP token account...");
-        if (!signTransaction) {
-          throw new Error("Wallet does not support signing");
-        }
-        
-        const createAtaIx = createAssociatedTokenAccountInstruction(
-          publicKey,
-          userLp,
-          publicKey,
-          poolAccount.lpMint,
-          TOKEN_PROGRAM_ID
-        );
-        
-        // Send transaction to create ATA
-        const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
-        const createAtaTx = new Transaction({
-          feePayer: publicKey,
-          blockhash,
-          lastValidBlockHeight,
-        }).add(createAtaIx);
-        
-        const signed = await signTransaction(createAtaTx);
-        const createAtaSig = await connection.sendRawTransaction(signed.serialize());
-        await connection.confirmTransaction({
-          signature: createAtaSig,
-          blockhash,
-          lastValidBlockHeight,
-        });
-        console.log("LP token account created:", createAtaSig);
-      }
+      // The entered amounts are maximums; only the part matching the reserve
+      // ratio is deposited, so the minimums follow the expected deposit
+      const vaultA = await connection.getTokenAccountBalance(poolAccount.vaultA);
+      const vaultB = await connection.getTokenAccountBalance(poolAccount.vaultB);
+      const lpSupply = await connection.getTokenSupply(poolAccount.lpMint);
+      const expected = quoteDeposit(
+        BigInt(amountABN.toString()),
+        BigInt(amountBBN.toString()),
+        BigInt(vaultA.value.amount),
+        BigInt(vaultB.value.amount),
+        BigInt(lpSupply.value.amount)
+      );
+      const amountAMinBN = new BN(withSlippage(expected.amountA).toString());
+      const amountBMinBN = new BN(withSlippage(expected.amountB).toString());
+      const minLpTokensBN = new BN(withSlippage(expected.lpTokens).toString());
 
       // Call add_liquidity instruction
       const tx = await program.methods
-        .addLiquidity(amountABN, amountBBN, new BN(0)) // min_lp_tokens = 0 for now
+        .addLiquidity(
+          amountABN,
+          amountBBN,
+          amountAMinBN,
+          amountBMinBN,
+          minLpTokensBN,
+          deadlineFromNow()
+        )
         .accountsPartial({
           user: publicKey,
           pool: poolPda,
@@ -135,10 +126,12 @@ export function AddLiquidity({
           lpMint: poolAccount.lpMint,
           userTokenA: userTokenA,
           userTokenB: userTokenB,
-          userLp: userLp,
-          tokenProgram: new PublicKey(
-            "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
-          ),
+          userLp: null,
+          userLpAta: userLpAta,
+          position: position,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
@@ -253,7 +246,7 @@ export function AddLiquidity({
             disabled={loading}
             className="w-full px-4 py-3 bg-gray-900/50 border border-gray-600 rounded-lg text-white placeholder-gray-500 focus:ring-2 focus:ring-blue-500 focus:border-transparent disabled:opacity-50 disabled:cursor-not-allowed"
           />
-          <p className="text-xs text-gray-400 mt-1">Token A to deposit</p>
+          <p className="text-xs text-gray-400 mt-1">Maximum Token A to deposit</p>
         </div>
 
         {/* Amount B */}
@@ -273,7 +266,7 @@ export function AddLiquidity({
             disabled={loading}
             className="w-full px-4 py-3 bg-gray-900/50 border border-gray-600 rounded-lg text-white placeholder-gray-500 focus:ring-2 focus:ring-emerald-500 focus:border-transparent disabled:opacity-50 disabled:cursor-not-allowed"
           />
-          <p className="text-xs text-gray-400 mt-1">Token B to deposit</p>
+          <p className="text-xs text-gray-400 mt-1">Maximum Token B to deposit</p>
         </div>
 
         {/* Pool Address Display */}
@@ -306,9 +299,9 @@ export function AddLiquidity({
       {/* Info Box */}
       <div className="mt-6 p-4 bg-blue-500/10 border border-blue-500/20 rounded-lg">
         <p className="text-xs text-blue-300">
-          <strong>Note:</strong> The program will automatically calculate the
-          optimal deposit amounts based on the current pool ratio. You may
-          receive slightly different LP tokens than expected.
+          <strong>Note:</strong> Only the part of each amount matching the
+          current pool ratio is deposited. The transaction fails if the pool
+          moves by more than 0.5% before it lands.
         </p>
       </div>
     </div>
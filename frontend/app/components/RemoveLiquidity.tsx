@@ -1,10 +1,10 @@
 "use client";
 
 import { useState, useEffect } from "react";
-import { PublicKey, Transaction } from "@solana/web3.js";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
 import { useWallet, useConnection } from "@solana/wallet-adapter-react";
-import { getAssociatedTokenAddress, createAssociatedTokenAccountInstruction, TOKEN_PROGRAM_ID } from "@solana/spl-token";
-import { useAmmProgram, getPoolAddress } from "@/lib";
+import { getAssociatedTokenAddress, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { useAmmProgram, getPoolAddress, getPositionAddress, withSlippage, deadlineFromNow } from "@/lib";
 import { BN } from "@coral-xyz/anchor";
 
 interface RemoveLiquidityProps {
@@ -16,7 +16,7 @@ export function RemoveLiquidity({
   tokenMintA,
   tokenMintB,
 }: RemoveLiquidityProps) {
-  const { publicKey, signTransaction } = useWallet();
+  const { publicKey } = useWallet();
   const { connection } = useConnection();
   const program = useAmmProgram();
 
@@ -157,7 +157,8 @@ export function RemoveLiquidity({
         return;
       }
 
-      // Get user's token accounts
+      // Get user's token accounts; the program creates the token accounts
+      // if they do not exist yet
       const userTokenA = await getAssociatedTokenAddress(
         mintAPubkey,
         publicKey
@@ -170,57 +171,7 @@ export function RemoveLiquidity({
         poolAccount.lpMint,
         publicKey
       );
-
-      // Check if token accounts exist, create if missing
-      const accountAInfo = await connection.getAccountInfo(userTokenA);
-      const accountBInfo = await connection.getAccountInfo(userTokenB);
-      
-      if (!accountAInfo || !accountBInfo) {
-        console.log("Creating missing token accounts...");
-        if (!signTransaction) {
-          throw new Error("Wallet does not support signing");
-        }
-        
-        const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
-        const createAtaTx = new Transaction({
-          feePayer: publicKey,
-          blockhash,
-          lastValidBlockHeight,
-        });
-        
-        if (!accountAInfo) {
-          createAtaTx.add(
-            createAssociatedTokenAccountInstruction(
-              publicKey,
-              userTokenA,
-              publicKey,
-              mintAPubkey,
-              TOKEN_PROGRAM_ID
-            )
-          );
-        }
-        
-        if (!accountBInfo) {
-          createAtaTx.add(
-            createAssociatedTokenAccountInstruction(
-              publicKey,
-              userTokenB,
-              publicKey,
-              mintBPubkey,
-              TOKEN_PROGRAM_ID
-            )
-          );
-        }
-        
-        const signed = await signTransaction(createAtaTx);
-        const createAtaSig = await connection.sendRawTransaction(signed.serialize());
-        await connection.confirmTransaction({
-          signature: createAtaSig,
-          blockhash,
-          lastValidBlockHeight,
-        });
-        console.log("Token accounts created:", createAtaSig);
-      }
+      const [position] = getPositionAddress(poolPda, publicKey);
 
       // Get balances before
       const vaultABefore = await connection.getTokenAccountBalance(
@@ -240,9 +191,18 @@ export function RemoveLiquidity({
       const expectedA = (lpAmountBI * reserveA) / totalSupply;
       const expectedB = (lpAmountBI * reserveB) / totalSupply;
 
+      // Fail rather than withdraw less than expected if the pool moves first
+      const minAmountABN = new BN(withSlippage(expectedA).toString());
+      const minAmountBBN = new BN(withSlippage(expectedB).toString());
+
       // Call remove_liquidity instruction
       const tx = await program.methods
-        .removeLiquidity(lpAmountBN)
+        .removeLiquidity(
+          lpAmountBN,
+          minAmountABN,
+          minAmountBBN,
+          deadlineFromNow()
+        )
         .accountsPartial({
           user: publicKey,
           pool: poolPda,
@@ -252,9 +212,14 @@ export function RemoveLiquidity({
           vaultB: poolAccount.vaultB,
           lpMint: poolAccount.lpMint,
           userLp: userLp,
-          userTokenA: userTokenA,
-          userTokenB: userTokenB,
+          userTokenA: null,
+          userTokenB: null,
+          userTokenAAta: userTokenA,
+          userTokenBAta: userTokenB,
+          position: position,
           tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
@@ -1,10 +1,10 @@
 "use client";
 
 import { useState, useEffect } from "react";
-import { PublicKey, Transaction } from "@solana/web3.js";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
 import { useWallet, useConnection } from "@solana/wallet-adapter-react";
-import { getAssociatedTokenAddress, createAssociatedTokenAccountInstruction, TOKEN_PROGRAM_ID } from "@solana/spl-token";
-import { useAmmProgram, getPoolAddress } from "@/lib";
+import { getAssociatedTokenAddress, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { useAmmProgram, getPoolAddress, getConfigAddress, deadlineFromNow } from "@/lib";
 import { BN } from "@coral-xyz/anchor";
 
 interface SwapProps {
@@ -26,7 +26,7 @@ function isValidPublicKey(address: string): boolean {
 }
 
 export function Swap({ tokenMintA, tokenMintB }: SwapProps) {
-  const { publicKey, signTransaction } = useWallet();
+  const { publicKey } = useWallet();
   const { connection } = useConnection();
   const program = useAmmProgram();
 
@@ -169,42 +169,11 @@ export function Swap({ tokenMintA, tokenMintB }: SwapProps) {
       const inputMint = isAtoB ? mintAPubkey : mintBPubkey;
       const outputMint = isAtoB ? mintBPubkey : mintAPubkey;
 
-      // Get user's token accounts
+      // Get user's token accounts; the program creates the output token
+      // account if it does not exist yet
       const userInput = await getAssociatedTokenAddress(inputMint, publicKey);
-      const userOutput = await getAssociatedTokenAddress(outputMint, publicKey);
-
-      // Check if output token account exists, if not create it
-      const outputAccountInfo = await connection.getAccountInfo(userOutput);
-      if (!outputAccountInfo) {
-        console.log("Creating output token account...");
-        if (!signTransaction) {
-          throw new Error("Wallet does not support signing");
-        }
-        
-        const createAtaIx = createAssociatedTokenAccountInstruction(
-          publicKey,
-          userOutput,
-          publicKey,
-          outputMint,
-          TOKEN_PROGRAM_ID
-        );
-        
-        const { blockhash, lastValidBlockHeight } = await connection.getLatestBlockhash();
-        const createAtaTx = new Transaction({
-          feePayer: publicKey,
-          blockhash,
-          lastValidBlockHeight,
-        }).add(createAtaIx);
-        
-        const signed = await signTransaction(createAtaTx);
-        const createAtaSig = await connection.sendRawTransaction(signed.serialize());
-        await connection.confirmTransaction({
-          signature: createAtaSig,
-          blockhash,
-          lastValidBlockHeight,
-        });
-        console.log("Output token account created:", createAtaSig);
-      }
+      const userOutputAta = await getAssociatedTokenAddress(outputMint, publicKey);
+      const [configPda] = getConfigAddress();
 
       // Get vault addresses
       const vaultInput = isAtoB ? poolAccount.vaultA : poolAccount.vaultB;
@@ -212,17 +181,28 @@ export function Swap({ tokenMintA, tokenMintB }: SwapProps) {
 
       // Call swap instruction
       const tx = await program.methods
-        .swap(amountInBN, minAmountOutBN)
+        .swap(
+          amountInBN,
+          minAmountOutBN,
+          0, // referral_bps: no referrer
+          deadlineFromNow()
+        )
         .accountsPartial({
           user: publicKey,
           pool: poolPda,
+          config: configPda,
           tokenMintA: mintAPubkey,
           tokenMintB: mintBPubkey,
+          outputMint: outputMint,
           userInput: userInput,
-          userOutput: userOutput,
+          userOutput: null,
           vaultInput: vaultInput,
           vaultOutput: vaultOutput,
+          referrerToken: null,
+          userOutputAta: userOutputAta,
           tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
         })
         .rpc();
 
@@ -242,6 +222,8 @@ export function Swap({ tokenMintA, tokenMintB }: SwapProps) {
         friendlyError = "Not enough liquidity in pool for this swap size.";
       } else if (err.message?.includes("PoolNotReady") || err.message?.includes("6010")) {
         friendlyError = "Pool has no liquidity available.";
+      } else if (err.message?.includes("DeadlineExceeded") || err.message?.includes("6012")) {
+        friendlyError = "Transaction expired before it landed. Please try again.";
       } else if (err.message?.includes("insufficient funds")) {
         friendlyError = "Insufficient token balance in your wallet.";
       } else if (err.message?.includes("User rejected")) {
@@ -46,13 +46,11 @@ export type Amm = {
               },
               {
                 "kind": "account",
-                "path": "pool.token_mint_a",
-                "account": "pool"
+                "path": "pool"
               },
               {
                 "kind": "account",
-                "path": "pool.token_mint_b",
-                "account": "pool"
+                "path": "pool"
               }
             ]
           }
@@ -85,49 +83,201 @@ export type Amm = {
         },
         {
           "name": "userLp",
-          "writable": true
+          "docs": [
+            "Existing account receiving the LP tokens; pass this or `user_lp_ata`"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "userLpAta",
+          "docs": [
+            "The user's associated LP token account, created on first deposit so",
+            "new LPs need no setup instruction"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "lpMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "position",
+          "docs": [
+            "Tracks the deposit for fee and impermanent loss reporting"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
         },
         {
           "name": "tokenProgram",
           "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
         }
       ],
       "args": [
         {
-          "name": "amountA",
+          "name": "amountAMax",
           "type": "u64"
         },
         {
-          "name": "amountB",
+          "name": "amountBMax",
+          "type": "u64"
+        },
+        {
+          "name": "amountAMin",
+          "type": "u64"
+        },
+        {
+          "name": "amountBMin",
           "type": "u64"
         },
         {
           "name": "minLpTokens",
           "type": "u64"
+        },
+        {
+          "name": "deadline",
+          "type": {
+            "option": "i64"
+          }
         }
       ]
     },
     {
-      "name": "initializePool",
+      "name": "addLiquidityExactLp",
       "discriminator": [
-        95,
-        180,
-        10,
-        172,
-        84,
-        174,
-        232,
-        40
+        115,
+        192,
+        236,
+        16,
+        231,
+        132,
+        91,
+        23
       ],
       "accounts": [
         {
-          "name": "payer",
+          "name": "user",
           "writable": true,
           "signer": true
         },
         {
           "name": "pool",
-          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -141,52 +291,106 @@ export type Amm = {
               },
               {
                 "kind": "account",
-                "path": "tokenMintA"
+                "path": "pool"
               },
               {
                 "kind": "account",
-                "path": "tokenMintB"
+                "path": "pool"
               }
             ]
           }
         },
         {
-          "name": "tokenMintA"
+          "name": "vaultA",
+          "writable": true
         },
         {
-          "name": "tokenMintB"
+          "name": "vaultB",
+          "writable": true
         },
         {
-          "name": "vaultA"
+          "name": "lpMint",
+          "writable": true
         },
         {
-          "name": "vaultB"
+          "name": "userTokenA",
+          "writable": true
         },
         {
-          "name": "lpMint"
+          "name": "userTokenB",
+          "writable": true
         },
         {
-          "name": "systemProgram",
-          "address": "11111111111111111111111111111111"
+          "name": "userLp",
+          "writable": true
+        },
+        {
+          "name": "position",
+          "docs": [
+            "Tracks the deposit for fee and impermanent loss reporting"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
         },
         {
           "name": "tokenProgram",
           "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
         }
       ],
-      "args": []
+      "args": [
+        {
+          "name": "lpOut",
+          "type": "u64"
+        },
+        {
+          "name": "maxAmountA",
+          "type": "u64"
+        },
+        {
+          "name": "maxAmountB",
+          "type": "u64"
+        }
+      ]
     },
     {
-      "name": "removeLiquidity",
+      "name": "addLiquidityNative",
       "discriminator": [
-        80,
+        20,
         85,
         209,
-        72,
-        24,
-        206,
-        177,
-        108
+        55,
+        43,
+        37,
+        134,
+        35
       ],
       "accounts": [
         {
@@ -196,7 +400,6 @@ export type Amm = {
         },
         {
           "name": "pool",
-          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -210,23 +413,15 @@ export type Amm = {
               },
               {
                 "kind": "account",
-                "path": "pool.token_mint_a",
-                "account": "pool"
+                "path": "pool"
               },
               {
                 "kind": "account",
-                "path": "pool.token_mint_b",
-                "account": "pool"
+                "path": "pool"
               }
             ]
           }
         },
-        {
-          "name": "tokenMintA"
-        },
-        {
-          "name": "tokenMintB"
-        },
         {
           "name": "vaultA",
           "writable": true
@@ -240,16 +435,79 @@ export type Amm = {
           "writable": true
         },
         {
-          "name": "userLp",
+          "name": "userToken",
+          "docs": [
+            "User account for the non-native side of the pool"
+          ],
           "writable": true
         },
         {
-          "name": "userTokenA",
+          "name": "userLp",
           "writable": true
         },
         {
-          "name": "userTokenB",
-          "writable": true
+          "name": "position",
+          "docs": [
+            "Tracks the deposit for fee and impermanent loss reporting"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tempWsol",
+          "docs": [
+            "Temporary wSOL account, closed back to the user before the instruction ends"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  115,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "nativeMint",
+          "address": "So11111111111111111111111111111111111111112"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
         },
         {
           "name": "tokenProgram",
@@ -258,22 +516,44 @@ export type Amm = {
       ],
       "args": [
         {
-          "name": "lpAmount",
+          "name": "amountAMax",
+          "type": "u64"
+        },
+        {
+          "name": "amountBMax",
+          "type": "u64"
+        },
+        {
+          "name": "amountAMin",
+          "type": "u64"
+        },
+        {
+          "name": "amountBMin",
+          "type": "u64"
+        },
+        {
+          "name": "minLpTokens",
           "type": "u64"
+        },
+        {
+          "name": "deadline",
+          "type": {
+            "option": "i64"
+          }
         }
       ]
     },
     {
-      "name": "swap",
+      "name": "addLiquiditySingle",
       "discriminator": [
-        248,
-        198,
-        158,
+        247,
+        21,
+        240,
         145,
-        225,
-        117,
-        135,
-        200
+        37,
+        101,
+        229,
+        181
       ],
       "accounts": [
         {
@@ -283,7 +563,6 @@ export type Amm = {
         },
         {
           "name": "pool",
-          "writable": true,
           "pda": {
             "seeds": [
               {
@@ -297,57 +576,2598 @@ export type Amm = {
               },
               {
                 "kind": "account",
-                "path": "pool.token_mint_a",
-                "account": "pool"
+                "path": "pool"
               },
               {
                 "kind": "account",
-                "path": "pool.token_mint_b",
-                "account": "pool"
+                "path": "pool"
               }
             ]
           }
         },
         {
-          "name": "tokenMintA"
+          "name": "vaultA",
+          "writable": true
         },
         {
-          "name": "tokenMintB"
+          "name": "vaultB",
+          "writable": true
         },
         {
-          "name": "userInput",
+          "name": "lpMint",
           "writable": true
         },
         {
-          "name": "userOutput",
+          "name": "userTokenIn",
           "writable": true
         },
         {
-          "name": "vaultInput",
+          "name": "userLp",
           "writable": true
         },
         {
-          "name": "vaultOutput",
-          "writable": true
+          "name": "position",
+          "docs": [
+            "Tracks the deposit for fee and impermanent loss reporting"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
         },
         {
           "name": "tokenProgram",
           "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
         }
       ],
       "args": [
+        {
+          "name": "tokenIn",
+          "type": "pubkey"
+        },
         {
           "name": "amountIn",
           "type": "u64"
         },
         {
-          "name": "minimumAmountOut",
+          "name": "minLpTokens",
           "type": "u64"
         }
       ]
-    }
+    },
+    {
+      "name": "addRewardStream",
+      "discriminator": [
+        126,
+        23,
+        174,
+        31,
+        202,
+        0,
+        137,
+        186
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "farm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm.pool",
+                "account": "farm"
+              }
+            ]
+          }
+        },
+        {
+          "name": "rewardMint"
+        },
+        {
+          "name": "rewardVault",
+          "docs": [
+            "One vault per reward mint, so the same token cannot back two streams"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109,
+                  95,
+                  114,
+                  101,
+                  119,
+                  97,
+                  114,
+                  100
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm"
+              },
+              {
+                "kind": "account",
+                "path": "rewardMint"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "funder",
+          "type": "pubkey"
+        }
+      ]
+    },
+    {
+      "name": "claimAllRewards",
+      "discriminator": [
+        132,
+        203,
+        246,
+        173,
+        206,
+        240,
+        85,
+        120
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "farm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm.pool",
+                "account": "farm"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "claimRewards",
+      "discriminator": [
+        4,
+        144,
+        132,
+        71,
+        116,
+        23,
+        151,
+        80
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "farm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm.pool",
+                "account": "farm"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "rewardMint"
+        },
+        {
+          "name": "rewardVault",
+          "writable": true
+        },
+        {
+          "name": "userReward",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "rewardMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "streamIndex",
+          "type": "u8"
+        }
+      ]
+    },
+    {
+      "name": "closePool",
+      "discriminator": [
+        140,
+        189,
+        209,
+        23,
+        239,
+        62,
+        239,
+        11
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "writable": true
+        },
+        {
+          "name": "vaultB",
+          "writable": true
+        },
+        {
+          "name": "lpMint"
+        },
+        {
+          "name": "destinationA",
+          "docs": [
+            "Receives the token A left in `vault_a`; must belong to the admin"
+          ],
+          "writable": true
+        },
+        {
+          "name": "destinationB",
+          "docs": [
+            "Receives the token B left in `vault_b`; must belong to the admin"
+          ],
+          "writable": true
+        },
+        {
+          "name": "recipient",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "emergencyUnstakeLp",
+      "discriminator": [
+        42,
+        133,
+        80,
+        172,
+        157,
+        47,
+        194,
+        41
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "farm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm.pool",
+                "account": "farm"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "lpVault",
+          "writable": true
+        },
+        {
+          "name": "userLp",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "fundRewardStream",
+      "discriminator": [
+        30,
+        148,
+        153,
+        242,
+        26,
+        108,
+        237,
+        155
+      ],
+      "accounts": [
+        {
+          "name": "funder",
+          "signer": true
+        },
+        {
+          "name": "farm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm.pool",
+                "account": "farm"
+              }
+            ]
+          }
+        },
+        {
+          "name": "rewardVault",
+          "writable": true
+        },
+        {
+          "name": "funderReward",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "streamIndex",
+          "type": "u8"
+        },
+        {
+          "name": "amount",
+          "type": "u64"
+        },
+        {
+          "name": "duration",
+          "type": "i64"
+        }
+      ]
+    },
+    {
+      "name": "getPositionStats",
+      "discriminator": [
+        38,
+        217,
+        13,
+        144,
+        181,
+        82,
+        26,
+        98
+      ],
+      "accounts": [
+        {
+          "name": "pool",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA"
+        },
+        {
+          "name": "vaultB"
+        },
+        {
+          "name": "lpMint"
+        },
+        {
+          "name": "position",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "position.owner",
+                "account": "lpPosition"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [],
+      "returns": {
+        "defined": {
+          "name": "positionStats"
+        }
+      }
+    },
+    {
+      "name": "initializeConfig",
+      "discriminator": [
+        208,
+        127,
+        21,
+        1,
+        194,
+        190,
+        196,
+        70
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializeFarm",
+      "discriminator": [
+        252,
+        28,
+        185,
+        172,
+        244,
+        74,
+        117,
+        165
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "pool",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "farm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "lpMint"
+        },
+        {
+          "name": "lpVault",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109,
+                  95,
+                  108,
+                  112
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm"
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "initializePool",
+      "discriminator": [
+        95,
+        180,
+        10,
+        172,
+        84,
+        174,
+        232,
+        40
+      ],
+      "accounts": [
+        {
+          "name": "payer",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "pool",
+          "docs": [
+            "Pool PDA - Anchor's `init` constraint automatically prevents duplicate pools",
+            "If a pool already exists for this token pair, the transaction will fail"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenMintA"
+              },
+              {
+                "kind": "account",
+                "path": "tokenMintB"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenMintA"
+        },
+        {
+          "name": "tokenMintB"
+        },
+        {
+          "name": "vaultA"
+        },
+        {
+          "name": "vaultB"
+        },
+        {
+          "name": "lpMint"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migrateConfig",
+      "discriminator": [
+        92,
+        131,
+        58,
+        105,
+        210,
+        154,
+        224,
+        193
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "migratePool",
+      "discriminator": [
+        55,
+        170,
+        171,
+        123,
+        210,
+        69,
+        39,
+        172
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "pool",
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": []
+    },
+    {
+      "name": "recoverTokens",
+      "discriminator": [
+        156,
+        18,
+        205,
+        212,
+        197,
+        254,
+        126,
+        142
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "pool",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "source",
+          "docs": [
+            "Pool-owned token account holding the stray tokens"
+          ],
+          "writable": true
+        },
+        {
+          "name": "destination",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "removeLiquidity",
+      "discriminator": [
+        80,
+        85,
+        209,
+        72,
+        24,
+        206,
+        177,
+        108
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenMintA"
+        },
+        {
+          "name": "tokenMintB"
+        },
+        {
+          "name": "vaultA",
+          "writable": true
+        },
+        {
+          "name": "vaultB",
+          "writable": true
+        },
+        {
+          "name": "lpMint",
+          "writable": true
+        },
+        {
+          "name": "userLp",
+          "writable": true
+        },
+        {
+          "name": "userTokenA",
+          "docs": [
+            "Existing account receiving token A; pass this or `user_token_a_ata`"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "userTokenB",
+          "docs": [
+            "Existing account receiving token B; pass this or `user_token_b_ata`"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "userTokenAAta",
+          "docs": [
+            "The user's associated token accounts for the pool tokens, created on",
+            "demand so withdrawals need no setup instruction"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenMintA"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "userTokenBAta",
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "tokenMintB"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "lpAmount",
+          "type": "u64"
+        },
+        {
+          "name": "minAmountA",
+          "type": "u64"
+        },
+        {
+          "name": "minAmountB",
+          "type": "u64"
+        },
+        {
+          "name": "deadline",
+          "type": {
+            "option": "i64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "removeLiquidityExactOut",
+      "discriminator": [
+        43,
+        143,
+        91,
+        100,
+        21,
+        99,
+        38,
+        149
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenMintA"
+        },
+        {
+          "name": "tokenMintB"
+        },
+        {
+          "name": "vaultA",
+          "writable": true
+        },
+        {
+          "name": "vaultB",
+          "writable": true
+        },
+        {
+          "name": "lpMint",
+          "writable": true
+        },
+        {
+          "name": "userLp",
+          "writable": true
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userTokenA",
+          "writable": true
+        },
+        {
+          "name": "userTokenB",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amountA",
+          "type": "u64"
+        },
+        {
+          "name": "amountB",
+          "type": "u64"
+        },
+        {
+          "name": "maxLpIn",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "removeLiquidityNative",
+      "discriminator": [
+        201,
+        174,
+        103,
+        101,
+        210,
+        247,
+        202,
+        35
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "writable": true
+        },
+        {
+          "name": "vaultB",
+          "writable": true
+        },
+        {
+          "name": "lpMint",
+          "writable": true
+        },
+        {
+          "name": "userLp",
+          "writable": true
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userToken",
+          "docs": [
+            "User account for the non-native side of the pool"
+          ],
+          "writable": true
+        },
+        {
+          "name": "tempWsol",
+          "docs": [
+            "Temporary wSOL account, closed back to the user before the instruction ends"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  115,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "nativeMint",
+          "address": "So11111111111111111111111111111111111111112"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "lpAmount",
+          "type": "u64"
+        },
+        {
+          "name": "minAmountA",
+          "type": "u64"
+        },
+        {
+          "name": "minAmountB",
+          "type": "u64"
+        },
+        {
+          "name": "deadline",
+          "type": {
+            "option": "i64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "removeLiquiditySingle",
+      "discriminator": [
+        216,
+        47,
+        132,
+        215,
+        169,
+        108,
+        188,
+        17
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultA",
+          "writable": true
+        },
+        {
+          "name": "vaultB",
+          "writable": true
+        },
+        {
+          "name": "lpMint",
+          "writable": true
+        },
+        {
+          "name": "userLp",
+          "writable": true
+        },
+        {
+          "name": "position",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  115,
+                  105,
+                  116,
+                  105,
+                  111,
+                  110
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userTokenOut",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "lpAmount",
+          "type": "u64"
+        },
+        {
+          "name": "tokenOut",
+          "type": "pubkey"
+        },
+        {
+          "name": "minAmountOut",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "setEmergencyMode",
+      "discriminator": [
+        79,
+        138,
+        190,
+        94,
+        0,
+        162,
+        205,
+        253
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "signer": true
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "enabled",
+          "type": "bool"
+        }
+      ]
+    },
+    {
+      "name": "setMaxReferralBps",
+      "discriminator": [
+        101,
+        68,
+        71,
+        40,
+        15,
+        10,
+        118,
+        223
+      ],
+      "accounts": [
+        {
+          "name": "admin",
+          "signer": true
+        },
+        {
+          "name": "config",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        }
+      ],
+      "args": [
+        {
+          "name": "maxReferralBps",
+          "type": "u16"
+        }
+      ]
+    },
+    {
+      "name": "stakeLp",
+      "discriminator": [
+        48,
+        168,
+        125,
+        78,
+        82,
+        71,
+        152,
+        117
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "farm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm.pool",
+                "account": "farm"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "lpVault",
+          "writable": true
+        },
+        {
+          "name": "userLp",
+          "writable": true
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    },
+    {
+      "name": "swap",
+      "discriminator": [
+        248,
+        198,
+        158,
+        145,
+        225,
+        117,
+        135,
+        200
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "config",
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  99,
+                  111,
+                  110,
+                  102,
+                  105,
+                  103
+                ]
+              }
+            ]
+          }
+        },
+        {
+          "name": "tokenMintA"
+        },
+        {
+          "name": "tokenMintB"
+        },
+        {
+          "name": "outputMint",
+          "docs": [
+            "Mint of the token being bought, used to derive `user_output_ata`"
+          ]
+        },
+        {
+          "name": "userInput",
+          "writable": true
+        },
+        {
+          "name": "userOutput",
+          "docs": [
+            "Existing account receiving the output; pass this or `user_output_ata`"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "vaultInput",
+          "writable": true
+        },
+        {
+          "name": "vaultOutput",
+          "writable": true
+        },
+        {
+          "name": "referrerToken",
+          "docs": [
+            "Optional integrator account receiving the referral cut, in the input token"
+          ],
+          "writable": true,
+          "optional": true
+        },
+        {
+          "name": "userOutputAta",
+          "docs": [
+            "The user's associated token account for the output mint, created on the",
+            "first purchase of this token so new users need no setup instruction"
+          ],
+          "writable": true,
+          "optional": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "account",
+                "path": "user"
+              },
+              {
+                "kind": "const",
+                "value": [
+                  6,
+                  221,
+                  246,
+                  225,
+                  215,
+                  101,
+                  161,
+                  147,
+                  217,
+                  203,
+                  225,
+                  70,
+                  206,
+                  235,
+                  121,
+                  172,
+                  28,
+                  180,
+                  133,
+                  237,
+                  95,
+                  91,
+                  55,
+                  145,
+                  58,
+                  140,
+                  245,
+                  133,
+                  126,
+                  255,
+                  0,
+                  169
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "outputMint"
+              }
+            ],
+            "program": {
+              "kind": "const",
+              "value": [
+                140,
+                151,
+                37,
+                143,
+                78,
+                36,
+                137,
+                241,
+                187,
+                61,
+                16,
+                41,
+                20,
+                142,
+                13,
+                131,
+                11,
+                90,
+                19,
+                153,
+                218,
+                255,
+                16,
+                132,
+                4,
+                142,
+                123,
+                216,
+                219,
+                233,
+                248,
+                89
+              ]
+            }
+          }
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        },
+        {
+          "name": "associatedTokenProgram",
+          "address": "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        }
+      ],
+      "args": [
+        {
+          "name": "amountIn",
+          "type": "u64"
+        },
+        {
+          "name": "minimumAmountOut",
+          "type": "u64"
+        },
+        {
+          "name": "referralBps",
+          "type": "u16"
+        },
+        {
+          "name": "deadline",
+          "type": {
+            "option": "i64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "swapNative",
+      "discriminator": [
+        88,
+        147,
+        177,
+        186,
+        129,
+        234,
+        239,
+        231
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "writable": true,
+          "signer": true
+        },
+        {
+          "name": "pool",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  112,
+                  111,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              },
+              {
+                "kind": "account",
+                "path": "pool"
+              }
+            ]
+          }
+        },
+        {
+          "name": "vaultInput",
+          "writable": true
+        },
+        {
+          "name": "vaultOutput",
+          "writable": true
+        },
+        {
+          "name": "userToken",
+          "docs": [
+            "User account for the non-native side of the pool"
+          ],
+          "writable": true
+        },
+        {
+          "name": "tempWsol",
+          "docs": [
+            "Temporary wSOL account, closed back to the user before the instruction ends"
+          ],
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  119,
+                  115,
+                  111,
+                  108
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "nativeMint",
+          "address": "So11111111111111111111111111111111111111112"
+        },
+        {
+          "name": "systemProgram",
+          "address": "11111111111111111111111111111111"
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amountIn",
+          "type": "u64"
+        },
+        {
+          "name": "minimumAmountOut",
+          "type": "u64"
+        },
+        {
+          "name": "deadline",
+          "type": {
+            "option": "i64"
+          }
+        }
+      ]
+    },
+    {
+      "name": "unstakeLp",
+      "discriminator": [
+        114,
+        4,
+        7,
+        206,
+        251,
+        176,
+        233,
+        119
+      ],
+      "accounts": [
+        {
+          "name": "user",
+          "signer": true
+        },
+        {
+          "name": "farm",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  102,
+                  97,
+                  114,
+                  109
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm.pool",
+                "account": "farm"
+              }
+            ]
+          }
+        },
+        {
+          "name": "userStake",
+          "writable": true,
+          "pda": {
+            "seeds": [
+              {
+                "kind": "const",
+                "value": [
+                  115,
+                  116,
+                  97,
+                  107,
+                  101
+                ]
+              },
+              {
+                "kind": "account",
+                "path": "farm"
+              },
+              {
+                "kind": "account",
+                "path": "user"
+              }
+            ]
+          }
+        },
+        {
+          "name": "lpVault",
+          "writable": true
+        },
+        {
+          "name": "userLp",
+          "writable": true
+        },
+        {
+          "name": "tokenProgram",
+          "address": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        }
+      ],
+      "args": [
+        {
+          "name": "amount",
+          "type": "u64"
+        }
+      ]
+    }
   ],
   "accounts": [
+    {
+      "name": "config",
+      "discriminator": [
+        155,
+        12,
+        170,
+        224,
+        30,
+        250,
+        204,
+        130
+      ]
+    },
+    {
+      "name": "farm",
+      "discriminator": [
+        161,
+        156,
+        211,
+        253,
+        250,
+        64,
+        53,
+        250
+      ]
+    },
+    {
+      "name": "lpPosition",
+      "discriminator": [
+        105,
+        241,
+        37,
+        200,
+        224,
+        2,
+        252,
+        90
+      ]
+    },
     {
       "name": "pool",
       "discriminator": [
@@ -360,6 +3180,34 @@ export type Amm = {
         109,
         188
       ]
+    },
+    {
+      "name": "userStake",
+      "discriminator": [
+        102,
+        53,
+        163,
+        107,
+        9,
+        138,
+        87,
+        153
+      ]
+    }
+  ],
+  "events": [
+    {
+      "name": "swapEvent",
+      "discriminator": [
+        64,
+        198,
+        205,
+        232,
+        38,
+        8,
+        113,
+        226
+      ]
     }
   ],
   "errors": [
@@ -394,22 +3242,296 @@ export type Amm = {
       "msg": "Math operation overflow"
     },
     {
-      "code": 6006,
-      "name": "invalidVault",
-      "msg": "Invalid vault account"
+      "code": 6006,
+      "name": "invalidVault",
+      "msg": "Invalid vault account"
+    },
+    {
+      "code": 6007,
+      "name": "invalidLpMint",
+      "msg": "Invalid LP mint"
+    },
+    {
+      "code": 6008,
+      "name": "unauthorized",
+      "msg": "Unauthorized: only admin can perform this action"
+    },
+    {
+      "code": 6009,
+      "name": "poolAlreadyExists",
+      "msg": "Pool already exists for this token pair"
+    },
+    {
+      "code": 6010,
+      "name": "poolNotReady",
+      "msg": "Pool not ready: reserves must be greater than zero"
+    },
+    {
+      "code": 6011,
+      "name": "invalidMint",
+      "msg": "Token mint does not belong to this pool"
+    },
+    {
+      "code": 6012,
+      "name": "deadlineExceeded",
+      "msg": "Transaction deadline has passed"
+    },
+    {
+      "code": 6013,
+      "name": "referralFeeTooHigh",
+      "msg": "Referral fee exceeds the configured maximum"
+    },
+    {
+      "code": 6014,
+      "name": "poolNotEmpty",
+      "msg": "Pool still holds liquidity"
+    },
+    {
+      "code": 6015,
+      "name": "emergencyMode",
+      "msg": "Pool is in emergency mode: only withdrawals are allowed"
+    },
+    {
+      "code": 6016,
+      "name": "cannotRecoverPoolTokens",
+      "msg": "Pool reserve and LP tokens cannot be recovered"
+    },
+    {
+      "code": 6017,
+      "name": "invalidRewardDuration",
+      "msg": "Reward duration must be greater than zero"
+    },
+    {
+      "code": 6018,
+      "name": "insufficientStake",
+      "msg": "Unstake amount exceeds staked balance"
+    },
+    {
+      "code": 6019,
+      "name": "invalidRewardStream",
+      "msg": "Reward stream index is invalid or not initialized"
+    },
+    {
+      "code": 6020,
+      "name": "tooManyRewardStreams",
+      "msg": "Farm already has the maximum number of reward streams"
+    },
+    {
+      "code": 6021,
+      "name": "alreadyMigrated",
+      "msg": "Account is already at the current layout version"
+    },
+    {
+      "code": 6022,
+      "name": "invalidOutputAccount",
+      "msg": "Pass either an existing output token account or the associated token account to create, not both"
+    }
+  ],
+  "types": [
+    {
+      "name": "config",
+      "docs": [
+        "Global config account holding admin authority"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "admin",
+            "docs": [
+              "Admin authority for protocol governance"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "PDA bump seed"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "maxReferralBps",
+            "docs": [
+              "Upper bound on the referral fee integrators may take on swaps, in basis points"
+            ],
+            "type": "u16"
+          },
+          {
+            "name": "version",
+            "docs": [
+              "Layout version, `CONFIG_VERSION` once created or migrated"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "reserved",
+            "docs": [
+              "Space for future fields, zero until used"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                64
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "farm",
+      "docs": [
+        "Farm distributing up to `MAX_REWARD_STREAMS` reward tokens to LP tokens staked for a pool"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "docs": [
+              "Pool whose LP tokens are staked"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "lpMint",
+            "docs": [
+              "LP token mint of the pool"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "lpVault",
+            "docs": [
+              "Vault holding staked LP tokens"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "totalStaked",
+            "docs": [
+              "Total LP tokens currently staked"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "PDA bump seed"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "rewardStreams",
+            "docs": [
+              "Concurrent reward streams, each with its own funder and schedule"
+            ],
+            "type": {
+              "array": [
+                {
+                  "defined": {
+                    "name": "rewardStream"
+                  }
+                },
+                3
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "lpPosition",
+      "docs": [
+        "A user's liquidity in a pool, tracked for fee and impermanent loss reporting.",
+        "LP minted and burned by every liquidity instruction is counted; LP received",
+        "by transfer is not."
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "docs": [
+              "Pool the position belongs to"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "docs": [
+              "Liquidity provider"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "lpAmount",
+            "docs": [
+              "LP tokens attributed to this position"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "entryAmountA",
+            "docs": [
+              "Token A deposited for the LP still held, reduced pro-rata on withdrawal"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "entryAmountB",
+            "docs": [
+              "Token B deposited for the LP still held, reduced pro-rata on withdrawal"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "entrySqrtKPerLp",
+            "docs": [
+              "LP-weighted average of `sqrt(k) / lp_supply` at entry, scaled by `FEE_GROWTH_PRECISION`"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "openedAt",
+            "docs": [
+              "Time of the first deposit into the position"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "lastUpdateTs",
+            "docs": [
+              "Time of the last deposit or withdrawal"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "bump",
+            "docs": [
+              "PDA bump seed"
+            ],
+            "type": "u8"
+          }
+        ]
+      }
     },
-    {
-      "code": 6007,
-      "name": "invalidLpMint",
-      "msg": "Invalid LP mint"
-    }
-  ],
-  "types": [
     {
       "name": "pool",
       "docs": [
-        "Pool account holding AMM state and vault references"
+        "Pool account holding AMM state and vault references.",
+        "",
+        "Zero-copy so instructions read it in place instead of Borsh-decoding it.",
+        "Handlers copy it out of `load()` rather than holding the borrow, since the",
+        "pool signs the token CPIs that follow.",
+        "The `#[repr(C)]` layout is part of the on-chain format: new fields are",
+        "carved out of `reserved` and existing fields are never reordered."
       ],
+      "serialization": "bytemuck",
+      "repr": {
+        "kind": "c"
+      },
       "type": {
         "kind": "struct",
         "fields": [
@@ -455,6 +3577,310 @@ export type Amm = {
             ],
             "type": "u16"
           },
+          {
+            "name": "bump",
+            "docs": [
+              "PDA bump seed"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "emergencyMode",
+            "docs": [
+              "Non-zero when only pro-rata withdrawals are allowed"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "version",
+            "docs": [
+              "Layout version, `POOL_VERSION` once created or migrated"
+            ],
+            "type": "u8"
+          },
+          {
+            "name": "padding",
+            "docs": [
+              "Aligns `reserved` to 8 bytes so it can hold `u64` fields"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                3
+              ]
+            }
+          },
+          {
+            "name": "reserved",
+            "docs": [
+              "Space for future fields such as oracle and fee accumulators"
+            ],
+            "type": {
+              "array": [
+                "u8",
+                128
+              ]
+            }
+          }
+        ]
+      }
+    },
+    {
+      "name": "positionStats",
+      "docs": [
+        "Fee income and impermanent loss of an LP position at current reserves"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "lpAmount",
+            "docs": [
+              "LP tokens attributed to the position"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "amountA",
+            "docs": [
+              "Token A the position redeems for now, fees included"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "amountB",
+            "docs": [
+              "Token B the position redeems for now, fees included"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feesA",
+            "docs": [
+              "Part of `amount_a` earned from swap fees"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feesB",
+            "docs": [
+              "Part of `amount_b` earned from swap fees"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "feeGrowth",
+            "docs": [
+              "Growth in `sqrt(k)` per LP token since entry, scaled by `FEE_GROWTH_PRECISION`"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "holdValueB",
+            "docs": [
+              "Deposited tokens valued at the current price, in token B"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "positionValueB",
+            "docs": [
+              "Position excluding fees valued at the current price, in token B"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "impermanentLossBps",
+            "docs": [
+              "Impermanent loss in basis points of `hold_value_b`"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "ageSeconds",
+            "docs": [
+              "Seconds since the position was opened"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "rewardStream",
+      "docs": [
+        "A single reward token emitted to a farm's stakers"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "rewardMint",
+            "docs": [
+              "Token paid out as rewards, default when the slot is unused"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "rewardVault",
+            "docs": [
+              "Vault holding undistributed rewards"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "funder",
+            "docs": [
+              "Authority allowed to fund and extend this stream"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "rewardPerSecond",
+            "docs": [
+              "Rewards emitted per second across all stakers"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "accRewardPerShare",
+            "docs": [
+              "Rewards accumulated per staked LP token, scaled by `REWARD_PRECISION`"
+            ],
+            "type": "u128"
+          },
+          {
+            "name": "lastUpdateTs",
+            "docs": [
+              "Last time `acc_reward_per_share` was brought up to date"
+            ],
+            "type": "i64"
+          },
+          {
+            "name": "endTs",
+            "docs": [
+              "Time at which the current reward period ends"
+            ],
+            "type": "i64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "swapEvent",
+      "docs": [
+        "Emitted after every successful swap"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pool",
+            "docs": [
+              "Pool the swap was executed against"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "user",
+            "docs": [
+              "Trader that signed the swap"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "inputMint",
+            "docs": [
+              "Mint of the token sold"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "amountIn",
+            "docs": [
+              "Full amount taken from the trader, including the referral cut"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "amountOut",
+            "docs": [
+              "Amount received by the trader"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "referrer",
+            "docs": [
+              "Token account that received the referral cut, if any"
+            ],
+            "type": {
+              "option": "pubkey"
+            }
+          },
+          {
+            "name": "referralAmount",
+            "docs": [
+              "Referral cut taken out of `amount_in` before pricing"
+            ],
+            "type": "u64"
+          }
+        ]
+      }
+    },
+    {
+      "name": "userStake",
+      "docs": [
+        "A user's staked LP position in a farm"
+      ],
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "farm",
+            "docs": [
+              "Farm the position belongs to"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "owner",
+            "docs": [
+              "Owner of the staked LP tokens"
+            ],
+            "type": "pubkey"
+          },
+          {
+            "name": "amount",
+            "docs": [
+              "LP tokens staked"
+            ],
+            "type": "u64"
+          },
+          {
+            "name": "rewardDebts",
+            "docs": [
+              "Per-stream `amount * acc_reward_per_share` at the last settlement, unscaled"
+            ],
+            "type": {
+              "array": [
+                "u128",
+                3
+              ]
+            }
+          },
+          {
+            "name": "pendingRewards",
+            "docs": [
+              "Per-stream settled rewards not yet claimed"
+            ],
+            "type": {
+              "array": [
+                "u64",
+                3
+              ]
+            }
+          },
           {
             "name": "bump",
             "docs": [
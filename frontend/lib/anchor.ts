@@ -66,6 +66,27 @@ export function getPoolAddress(
   return [poolPda, bump];
 }
 
+/**
+ * Derive the global Config PDA
+ */
+export function getConfigAddress(): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync([Buffer.from("config")], PROGRAM_ID);
+}
+
+/**
+ * Derive the LP position PDA of `owner` in `pool`
+ */
+export function getPositionAddress(
+  pool: PublicKey,
+  owner: PublicKey
+): [PublicKey, number] {
+  const [positionPda, bump] = PublicKey.findProgramAddressSync(
+    [Buffer.from("position"), pool.toBuffer(), owner.toBuffer()],
+    PROGRAM_ID
+  );
+  return [positionPda, bump];
+}
+
 /**
  * Verify program deployment on-chain (optional runtime check)
  * Returns true if program exists and is executable
@@ -0,0 +1,13 @@
+import { BN } from "@coral-xyz/anchor";
+
+/**
+ * How long a signed transaction stays valid, in seconds
+ */
+export const DEADLINE_SECONDS = 300;
+
+/**
+ * Unix timestamp after which the program rejects the transaction
+ */
+export function deadlineFromNow(seconds: number = DEADLINE_SECONDS): BN {
+  return new BN(Math.floor(Date.now() / 1000) + seconds);
+}
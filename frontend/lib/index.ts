@@ -3,12 +3,20 @@ export {
   getProvider, 
   getAmmProgram, 
   getPoolAddress, 
+  getConfigAddress, 
+  getPositionAddress, 
   verifyProgramDeployment,
   PROGRAM_ID,
   DEPLOYED_PROGRAM_ID 
 } from "./anchor";
 export type { AmmProgram } from "./anchor";
 
+// Liquidity math
+export { LIQUIDITY_SLIPPAGE_BPS, withSlippage, quoteDeposit } from "./liquidity";
+
+// Transaction deadlines
+export { DEADLINE_SECONDS, deadlineFromNow } from "./deadline";
+
 // React hooks
 export { useAmmProgram } from "./useAmmProgram";
 export { useIsAdmin } from "./useIsAdmin";
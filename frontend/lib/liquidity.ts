@@ -0,0 +1,54 @@
+/**
+ * Slippage tolerance applied to the minimums of liquidity instructions,
+ * in basis points (0.5%)
+ */
+export const LIQUIDITY_SLIPPAGE_BPS = 50;
+
+/**
+ * Lower an expected amount by the slippage tolerance
+ */
+export function withSlippage(
+  amount: bigint,
+  slippageBps: number = LIQUIDITY_SLIPPAGE_BPS
+): bigint {
+  return (amount * BigInt(10_000 - slippageBps)) / BigInt(10_000);
+}
+
+function integerSqrt(value: bigint): bigint {
+  if (value < BigInt(2)) return value;
+  let x = value;
+  let y = (x + BigInt(1)) / BigInt(2);
+  while (y < x) {
+    x = y;
+    y = (x + value / x) / BigInt(2);
+  }
+  return x;
+}
+
+/**
+ * Amounts add_liquidity takes for the given maximums and the LP tokens it
+ * mints, following the program's rule: the first deposit takes both maximums,
+ * later ones only what matches the reserve ratio
+ */
+export function quoteDeposit(
+  amountAMax: bigint,
+  amountBMax: bigint,
+  reserveA: bigint,
+  reserveB: bigint,
+  lpSupply: bigint
+): { amountA: bigint; amountB: bigint; lpTokens: bigint } {
+  if (lpSupply === BigInt(0)) {
+    return {
+      amountA: amountAMax,
+      amountB: amountBMax,
+      lpTokens: integerSqrt(amountAMax * amountBMax),
+    };
+  }
+
+  const aBinds = amountAMax * reserveB < (amountBMax + BigInt(1)) * reserveA;
+  const amountA = aBinds ? amountAMax : (amountBMax * reserveA) / reserveB;
+  const amountB = aBinds ? (amountAMax * reserveB) / reserveA : amountBMax;
+  const lpA = (amountA * lpSupply) / reserveA;
+  const lpB = (amountB * lpSupply) / reserveB;
+  return { amountA, amountB, lpTokens: lpA < lpB ? lpA : lpB };
+}
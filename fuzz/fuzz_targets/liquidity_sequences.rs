@@ -0,0 +1,324 @@
+//! Random sequences of `add_liquidity`, `remove_liquidity` and `swap` from a
+//! handful of users against one pool. A ledger built from the users' own
+//! balance changes records what each user deposited, withdrew and swapped;
+//! after every step it must agree with the chain, and
+//! - each vault holds exactly what the ledger says flowed into it,
+//! - the vaults cover what all LP holders can redeem,
+//! - swaps never decrease `k`,
+//! - no step decreases `k` per LP token squared, i.e. the value behind each LP
+//!   token, so no sequence can extract more than it put in.
+
+#![no_main]
+
+use amm::math::U256;
+use amm_client::instructions::{self, args};
+use amm_client::PoolKeys;
+use amm_program_tests::TestEnv;
+use anchor_spl::associated_token::get_associated_token_address;
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+
+const USERS: usize = 3;
+const MAX_ACTIONS: usize = 32;
+
+#[derive(Arbitrary, Debug)]
+enum Action {
+    AddLiquidity { user: u8, amount_a: u32, amount_b: u32 },
+    RemoveLiquidity { user: u8, share_bps: u16 },
+    Swap { user: u8, a_to_b: bool, amount_in: u32 },
+}
+
+#[derive(Arbitrary, Debug)]
+struct Scenario {
+    seed_a: u32,
+    seed_b: u32,
+    actions: Vec<Action>,
+}
+
+/// Token and LP balances of one user
+#[derive(Clone, Copy)]
+struct Balances {
+    a: u64,
+    b: u64,
+    lp: u64,
+}
+
+/// Movements of one token between a user and its vault, seen from the user
+#[derive(Default, Clone, Copy)]
+struct Flows {
+    deposited: u128,
+    withdrawn: u128,
+    swapped_in: u128,
+    swapped_out: u128,
+}
+
+impl Flows {
+    fn add(&mut self, other: &Flows) {
+        self.deposited += other.deposited;
+        self.withdrawn += other.withdrawn;
+        self.swapped_in += other.swapped_in;
+        self.swapped_out += other.swapped_out;
+    }
+
+    /// What the vault must hold after these flows
+    fn vault_balance(&self) -> u128 {
+        (self.deposited + self.swapped_in)
+            .checked_sub(self.withdrawn + self.swapped_out)
+            .expect("vault paid out more than it received")
+    }
+}
+
+#[derive(Default)]
+struct UserLedger {
+    a: Flows,
+    b: Flows,
+    lp: u64,
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    AddLiquidity,
+    RemoveLiquidity,
+    Swap,
+}
+
+/// Per-user record of every token movement, kept independently of pool state
+struct Ledger {
+    users: Vec<UserLedger>,
+}
+
+impl Ledger {
+    fn new(users: usize) -> Self {
+        Self {
+            users: (0..users).map(|_| UserLedger::default()).collect(),
+        }
+    }
+
+    /// Record a step from the user's balances around it, checking that tokens
+    /// only moved the way the instruction allows
+    fn record(&mut self, user: usize, kind: Kind, before: Balances, after: Balances) {
+        let entry = &mut self.users[user];
+        let paid_a = before.a.saturating_sub(after.a);
+        let paid_b = before.b.saturating_sub(after.b);
+        let received_a = after.a.saturating_sub(before.a);
+        let received_b = after.b.saturating_sub(before.b);
+        let minted = after.lp.saturating_sub(before.lp);
+        let burned = before.lp.saturating_sub(after.lp);
+        match kind {
+            Kind::AddLiquidity => {
+                assert!(received_a == 0 && received_b == 0 && burned == 0, "deposit paid tokens out");
+                assert!(minted == 0 || paid_a > 0 || paid_b > 0, "LP minted for nothing");
+                entry.a.deposited += u128::from(paid_a);
+                entry.b.deposited += u128::from(paid_b);
+            }
+            Kind::RemoveLiquidity => {
+                assert!(paid_a == 0 && paid_b == 0 && minted == 0, "withdrawal took tokens in");
+                assert!(burned > 0 || (received_a == 0 && received_b == 0), "tokens withdrawn without burning LP");
+                entry.a.withdrawn += u128::from(received_a);
+                entry.b.withdrawn += u128::from(received_b);
+            }
+            Kind::Swap => {
+                assert!(minted == 0 && burned == 0, "swap moved LP tokens");
+                assert!(paid_a == 0 || paid_b == 0, "swap took both tokens");
+                assert!(received_a == 0 || received_b == 0, "swap paid out both tokens");
+                assert!(
+                    (received_a == 0 || paid_b > 0) && (received_b == 0 || paid_a > 0),
+                    "swap output without input"
+                );
+                entry.a.swapped_in += u128::from(paid_a);
+                entry.b.swapped_in += u128::from(paid_b);
+                entry.a.swapped_out += u128::from(received_a);
+                entry.b.swapped_out += u128::from(received_b);
+            }
+        }
+        entry.lp = entry.lp + minted - burned;
+    }
+
+    /// Flows of all users together, i.e. of the vaults
+    fn vaults(&self) -> (Flows, Flows) {
+        let mut a = Flows::default();
+        let mut b = Flows::default();
+        for user in &self.users {
+            a.add(&user.a);
+            b.add(&user.b);
+        }
+        (a, b)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct PoolState {
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+}
+
+impl PoolState {
+    async fn read(env: &mut TestEnv, keys: &PoolKeys) -> Self {
+        let (reserve_a, reserve_b, lp_supply) = env.reserves(keys).await;
+        Self {
+            reserve_a,
+            reserve_b,
+            lp_supply,
+        }
+    }
+
+    fn k(&self) -> U256 {
+        U256::from(self.reserve_a) * U256::from(self.reserve_b)
+    }
+}
+
+fuzz_target!(|scenario: Scenario| {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap()
+        .block_on(run(scenario));
+});
+
+async fn run(scenario: Scenario) {
+    let mut env = TestEnv::new().await;
+    let keys = env.empty_pool().await;
+
+    let mut users = Vec::new();
+    for _ in 0..=USERS {
+        users.push(env.new_user().await);
+    }
+    let mut ledger = Ledger::new(users.len());
+
+    // The first user seeds the pool
+    let seed = Action::AddLiquidity {
+        user: 0,
+        amount_a: scenario.seed_a.max(1),
+        amount_b: scenario.seed_b.max(1),
+    };
+    apply(&mut env, &keys, &users, &mut ledger, seed).await.unwrap();
+
+    let mut before = PoolState::read(&mut env, &keys).await;
+    for action in scenario.actions.into_iter().take(MAX_ACTIONS) {
+        let is_swap = matches!(action, Action::Swap { .. });
+        // Failed transactions are expected (zero amounts, dust, slippage); the
+        // invariants must hold either way
+        let _ = apply(&mut env, &keys, &users, &mut ledger, action).await;
+
+        let after = PoolState::read(&mut env, &keys).await;
+        check_claims(&mut env, &keys, &users, &ledger, after).await;
+        if is_swap {
+            assert!(after.k() >= before.k(), "swap decreased k");
+        }
+        // k / supply^2 must not decrease: k1 * s0^2 >= k0 * s1^2
+        let supply_before = U256::from(before.lp_supply);
+        let supply_after = U256::from(after.lp_supply);
+        if before.lp_supply > 0 && after.lp_supply > 0 {
+            assert!(
+                after.k() * supply_before * supply_before >= before.k() * supply_after * supply_after,
+                "value per LP token decreased"
+            );
+        }
+        before = after;
+    }
+}
+
+/// Fund and run one action, recording it in the ledger whether it succeeds or not
+async fn apply(
+    env: &mut TestEnv,
+    keys: &PoolKeys,
+    users: &[Keypair],
+    ledger: &mut Ledger,
+    action: Action,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (index, kind, instruction) = match action {
+        Action::AddLiquidity { user, amount_a, amount_b } => {
+            let index = user as usize % users.len();
+            let owner = users[index].pubkey();
+            env.fund_tokens(&keys.token_mint_a, &owner, amount_a.into()).await;
+            env.fund_tokens(&keys.token_mint_b, &owner, amount_b.into()).await;
+            let instruction = instructions::add_liquidity(&owner, keys, args::AddLiquidity {
+                amount_a_max: amount_a.into(),
+                amount_b_max: amount_b.into(),
+                amount_a_min: 0,
+                amount_b_min: 0,
+                min_lp_tokens: 0,
+                deadline: None,
+            });
+            (index, Kind::AddLiquidity, instruction)
+        }
+        Action::RemoveLiquidity { user, share_bps } => {
+            let index = user as usize % users.len();
+            let owner = users[index].pubkey();
+            let balance = balance_or_zero(env, &keys.lp_mint, &owner).await;
+            let lp_amount = (u128::from(balance) * u128::from(share_bps % 10_001) / 10_000) as u64;
+            let instruction = instructions::remove_liquidity(&owner, keys, args::RemoveLiquidity {
+                lp_amount,
+                min_amount_a: 0,
+                min_amount_b: 0,
+                deadline: None,
+            });
+            (index, Kind::RemoveLiquidity, instruction)
+        }
+        Action::Swap { user, a_to_b, amount_in } => {
+            let index = user as usize % users.len();
+            let owner = users[index].pubkey();
+            let input_mint = if a_to_b { keys.token_mint_a } else { keys.token_mint_b };
+            env.fund_tokens(&input_mint, &owner, amount_in.into()).await;
+            let instruction = instructions::swap(&owner, keys, &input_mint, None, args::Swap {
+                amount_in: amount_in.into(),
+                minimum_amount_out: 0,
+                referral_bps: 0,
+                deadline: None,
+            });
+            (index, Kind::Swap, instruction)
+        }
+    };
+
+    let user = &users[index];
+    let before = balances(env, keys, &user.pubkey()).await;
+    let result = env.process(&[instruction], &[user]).await;
+    let after = balances(env, keys, &user.pubkey()).await;
+    ledger.record(index, kind, before, after);
+    result?;
+    Ok(())
+}
+
+async fn balance_or_zero(env: &mut TestEnv, mint: &Pubkey, owner: &Pubkey) -> u64 {
+    let ata = get_associated_token_address(owner, mint);
+    if env.account_exists(&ata).await {
+        env.token_balance(&ata).await
+    } else {
+        0
+    }
+}
+
+async fn balances(env: &mut TestEnv, keys: &PoolKeys, owner: &Pubkey) -> Balances {
+    Balances {
+        a: balance_or_zero(env, &keys.token_mint_a, owner).await,
+        b: balance_or_zero(env, &keys.token_mint_b, owner).await,
+        lp: balance_or_zero(env, &keys.lp_mint, owner).await,
+    }
+}
+
+/// The chain must match the ledger, and every LP holder redeeming at once must
+/// not ask for more than the vaults hold
+async fn check_claims(env: &mut TestEnv, keys: &PoolKeys, users: &[Keypair], ledger: &Ledger, state: PoolState) {
+    let (vault_a, vault_b) = ledger.vaults();
+    assert_eq!(vault_a.vault_balance(), u128::from(state.reserve_a), "vault A differs from the ledger");
+    assert_eq!(vault_b.vault_balance(), u128::from(state.reserve_b), "vault B differs from the ledger");
+
+    let mut held = 0u64;
+    let mut claim_a = 0u128;
+    let mut claim_b = 0u128;
+    for (user, entry) in users.iter().zip(&ledger.users) {
+        let lp = balance_or_zero(env, &keys.lp_mint, &user.pubkey()).await;
+        assert_eq!(lp, entry.lp, "LP balance differs from the ledger");
+        held += entry.lp;
+        if state.lp_supply > 0 {
+            claim_a += u128::from(entry.lp) * vault_a.vault_balance() / u128::from(state.lp_supply);
+            claim_b += u128::from(entry.lp) * vault_b.vault_balance() / u128::from(state.lp_supply);
+        }
+    }
+    assert_eq!(held, state.lp_supply, "LP tokens outside the tracked users");
+    assert!(claim_a <= vault_a.vault_balance(), "vault A cannot cover LP claims");
+    assert!(claim_b <= vault_b.vault_balance(), "vault B cannot cover LP claims");
+}
@@ -24,5 +24,40 @@ pub enum AmmError {
     PoolAlreadyExists,
     #[msg("Pool not ready: reserves must be greater than zero")]
     PoolNotReady,
+    #[msg("Token mint does not belong to this pool")]
+    InvalidMint,
+    #[msg("Transaction deadline has passed")]
+    DeadlineExceeded,
+    #[msg("Referral fee exceeds the configured maximum")]
+    ReferralFeeTooHigh,
+    #[msg("Pool still holds liquidity")]
+    PoolNotEmpty,
+    #[msg("Pool is in emergency mode: only withdrawals are allowed")]
+    EmergencyMode,
+    #[msg("Pool reserve and LP tokens cannot be recovered")]
+    CannotRecoverPoolTokens,
+    #[msg("Reward duration must be greater than zero")]
+    InvalidRewardDuration,
+    #[msg("Unstake amount exceeds staked balance")]
+    InsufficientStake,
+    #[msg("Reward stream index is invalid or not initialized")]
+    InvalidRewardStream,
+    #[msg("Farm already has the maximum number of reward streams")]
+    TooManyRewardStreams,
+    #[msg("Account is already at the current layout version")]
+    AlreadyMigrated,
+    #[msg("Pass either an existing output token account or the associated token account to create, not both")]
+    InvalidOutputAccount,
 }
 
+
+impl From<amm_math::MathError> for AmmError {
+    fn from(error: amm_math::MathError) -> Self {
+        match error {
+            amm_math::MathError::Overflow => AmmError::MathOverflow,
+            amm_math::MathError::InsufficientLiquidity => AmmError::InsufficientLiquidity,
+            amm_math::MathError::ZeroLiquidity => AmmError::ZeroLiquidity,
+            amm_math::MathError::SlippageExceeded => AmmError::SlippageExceeded,
+        }
+    }
+}
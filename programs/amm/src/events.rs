@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+/// Emitted after every successful swap
+#[event]
+pub struct SwapEvent {
+    /// Pool the swap was executed against
+    pub pool: Pubkey,
+    /// Trader that signed the swap
+    pub user: Pubkey,
+    /// Mint of the token sold
+    pub input_mint: Pubkey,
+    /// Full amount taken from the trader, including the referral cut
+    pub amount_in: u64,
+    /// Amount received by the trader
+    pub amount_out: u64,
+    /// Token account that received the referral cut, if any
+    pub referrer: Option<Pubkey>,
+    /// Referral cut taken out of `amount_in` before pricing
+    pub referral_amount: u64,
+}
@@ -1,46 +1,44 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, Mint, Token, Transfer, MintTo};
 use anchor_lang::solana_program::program_option::COption;
-use crate::state::Pool;
+use crate::state::{LpPosition, Pool};
 use crate::error::AmmError;
-use crate::math::integer_sqrt;
+use crate::math::{get_deposit_amounts, get_sqrt_k_per_lp};
 
 pub fn add_liquidity(
     ctx: Context<AddLiquidity>,
-    amount_a: u64,
-    amount_b: u64,
+    amount_a_max: u64,
+    amount_b_max: u64,
+    amount_a_min: u64,
+    amount_b_min: u64,
     min_lp_tokens: u64,
+    deadline: Option<i64>,
 ) -> Result<()> {
-    require!(amount_a > 0 && amount_b > 0, AmmError::ZeroLiquidity);
+    if let Some(deadline) = deadline {
+        require!(Clock::get()?.unix_timestamp <= deadline, AmmError::DeadlineExceeded);
+    }
     
-    let pool = &ctx.accounts.pool;
+    require!(amount_a_max > 0 && amount_b_max > 0, AmmError::ZeroLiquidity);
+    
+    let pool = *ctx.accounts.pool.load()?;
+    let user_lp = match (&ctx.accounts.user_lp, &ctx.accounts.user_lp_ata) {
+        (Some(account), None) | (None, Some(account)) => account,
+        _ => return Err(AmmError::InvalidOutputAccount.into()),
+    };
     let reserve_a = ctx.accounts.vault_a.amount;
     let reserve_b = ctx.accounts.vault_b.amount;
     let lp_supply = ctx.accounts.lp_mint.supply;
     
-    let lp_tokens = if lp_supply == 0 {
-        // First liquidity provision
-        let liquidity = integer_sqrt((amount_a as u128).checked_mul(amount_b as u128).ok_or(AmmError::MathOverflow)?);
-        let lp = u64::try_from(liquidity).map_err(|_| AmmError::MathOverflow)?;
-        require!(lp > 0, AmmError::ZeroLiquidity);
-        lp
-    } else {
-        // Subsequent liquidity provision - proportional to existing reserves
-        let lp_a = (amount_a as u128)
-            .checked_mul(lp_supply as u128)
-            .ok_or(AmmError::MathOverflow)?
-            .checked_div(reserve_a as u128)
-            .ok_or(AmmError::MathOverflow)?;
-        
-        let lp_b = (amount_b as u128)
-            .checked_mul(lp_supply as u128)
-            .ok_or(AmmError::MathOverflow)?
-            .checked_div(reserve_b as u128)
-            .ok_or(AmmError::MathOverflow)?;
-        
-        let lp = lp_a.min(lp_b);
-        u64::try_from(lp).map_err(|_| AmmError::MathOverflow)?
-    };
+    let (amount_a, amount_b, lp_tokens) = get_deposit_amounts(
+        amount_a_max,
+        amount_b_max,
+        amount_a_min,
+        amount_b_min,
+        reserve_a,
+        reserve_b,
+        lp_supply,
+    )?;
     
     require!(lp_tokens >= min_lp_tokens, AmmError::SlippageExceeded);
     
@@ -84,7 +82,7 @@ pub fn add_liquidity(
             ctx.accounts.token_program.to_account_info(),
             MintTo {
                 mint: ctx.accounts.lp_mint.to_account_info(),
-                to: ctx.accounts.user_lp.to_account_info(),
+                to: user_lp.to_account_info(),
                 authority: ctx.accounts.pool.to_account_info(),
             },
             signer,
@@ -92,6 +90,17 @@ pub fn add_liquidity(
         lp_tokens,
     )?;
     
+    // Record the deposit against the post-deposit pool value per LP
+    let sqrt_k_per_lp = get_sqrt_k_per_lp(
+        reserve_a.checked_add(amount_a).ok_or(AmmError::MathOverflow)?,
+        reserve_b.checked_add(amount_b).ok_or(AmmError::MathOverflow)?,
+        lp_supply.checked_add(lp_tokens).ok_or(AmmError::MathOverflow)?,
+    )?;
+    
+    let position = &mut ctx.accounts.position;
+    position.open(ctx.accounts.pool.key(), ctx.accounts.user.key(), ctx.bumps.position);
+    position.record_deposit(lp_tokens, amount_a, amount_b, sqrt_k_per_lp, Clock::get()?.unix_timestamp)?;
+    
     Ok(())
 }
 
@@ -101,61 +110,85 @@ pub struct AddLiquidity<'info> {
     pub user: Signer<'info>,
     
     #[account(
-        seeds = [b"pool", pool.token_mint_a.as_ref(), pool.token_mint_b.as_ref()],
-        bump = pool.bump
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump,
+        constraint = !pool.load()?.is_emergency_mode() @ AmmError::EmergencyMode
     )]
-    pub pool: Box<Account<'info, Pool>>,
+    pub pool: AccountLoader<'info, Pool>,
     
-    #[account(address = pool.token_mint_a)]
+    #[account(address = pool.load()?.token_mint_a)]
     pub token_mint_a: Box<Account<'info, Mint>>,
     
-    #[account(address = pool.token_mint_b)]
+    #[account(address = pool.load()?.token_mint_b)]
     pub token_mint_b: Box<Account<'info, Mint>>,
     
     #[account(
         mut,
-        address = pool.vault_a,
-        constraint = vault_a.mint == pool.token_mint_a @ AmmError::InvalidVault,
+        address = pool.load()?.vault_a,
+        constraint = vault_a.mint == pool.load()?.token_mint_a @ AmmError::InvalidVault,
         constraint = vault_a.owner == pool.key() @ AmmError::InvalidVault
     )]
     pub vault_a: Box<Account<'info, anchor_spl::token::TokenAccount>>,
     
     #[account(
         mut,
-        address = pool.vault_b,
-        constraint = vault_b.mint == pool.token_mint_b @ AmmError::InvalidVault,
+        address = pool.load()?.vault_b,
+        constraint = vault_b.mint == pool.load()?.token_mint_b @ AmmError::InvalidVault,
         constraint = vault_b.owner == pool.key() @ AmmError::InvalidVault
     )]
     pub vault_b: Box<Account<'info, anchor_spl::token::TokenAccount>>,
     
     #[account(
         mut,
-        address = pool.lp_mint,
+        address = pool.load()?.lp_mint,
         constraint = lp_mint.mint_authority == COption::Some(pool.key()) @ AmmError::InvalidLpMint
     )]
     pub lp_mint: Box<Account<'info, Mint>>,
     
     #[account(
         mut,
-        constraint = user_token_a.mint == pool.token_mint_a,
+        constraint = user_token_a.mint == pool.load()?.token_mint_a,
         constraint = user_token_a.owner == user.key()
     )]
     pub user_token_a: Box<Account<'info, anchor_spl::token::TokenAccount>>,
     
     #[account(
         mut,
-        constraint = user_token_b.mint == pool.token_mint_b,
+        constraint = user_token_b.mint == pool.load()?.token_mint_b,
         constraint = user_token_b.owner == user.key()
     )]
     pub user_token_b: Box<Account<'info, anchor_spl::token::TokenAccount>>,
     
+    /// Existing account receiving the LP tokens; pass this or `user_lp_ata`
     #[account(
         mut,
-        constraint = user_lp.mint == pool.lp_mint,
+        constraint = user_lp.mint == lp_mint.key(),
         constraint = user_lp.owner == user.key()
     )]
-    pub user_lp: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    pub user_lp: Option<Box<Account<'info, anchor_spl::token::TokenAccount>>>,
+    
+    /// The user's associated LP token account, created on first deposit so
+    /// new LPs need no setup instruction
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = lp_mint,
+        associated_token::authority = user
+    )]
+    pub user_lp_ata: Option<Box<Account<'info, anchor_spl::token::TokenAccount>>>,
+    
+    /// Tracks the deposit for fee and impermanent loss reporting
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = LpPosition::LEN,
+        seeds = [b"position", pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, LpPosition>>,
     
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, Transfer, MintTo};
+use anchor_lang::solana_program::program_option::COption;
+use crate::state::{LpPosition, Pool};
+use crate::error::AmmError;
+use crate::math::{get_exact_lp_deposit_amounts, get_sqrt_k_per_lp};
+
+pub fn add_liquidity_exact_lp(
+    ctx: Context<AddLiquidityExactLp>,
+    lp_out: u64,
+    max_amount_a: u64,
+    max_amount_b: u64,
+) -> Result<()> {
+    require!(lp_out > 0, AmmError::ZeroLiquidity);
+    
+    let pool = *ctx.accounts.pool.load()?;
+    let reserve_a = ctx.accounts.vault_a.amount;
+    let reserve_b = ctx.accounts.vault_b.amount;
+    let lp_supply = ctx.accounts.lp_mint.supply;
+    
+    // Exact LP minting prices against existing reserves, so the pool must be seeded
+    require!(reserve_a > 0 && reserve_b > 0 && lp_supply > 0, AmmError::PoolNotReady);
+    
+    // Required deposits rounded up so the pool never mints LP below its share
+    let (amount_a, amount_b) = get_exact_lp_deposit_amounts(lp_out, reserve_a, reserve_b, lp_supply)?;
+    
+    require!(
+        amount_a <= max_amount_a && amount_b <= max_amount_b,
+        AmmError::SlippageExceeded
+    );
+    
+    // Transfer token A from user to vault
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_a.to_account_info(),
+                to: ctx.accounts.vault_a.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount_a,
+    )?;
+    
+    // Transfer token B from user to vault
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_b.to_account_info(),
+                to: ctx.accounts.vault_b.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount_b,
+    )?;
+    
+    // Mint LP tokens to user
+    let seeds = &[
+        b"pool",
+        pool.token_mint_a.as_ref(),
+        pool.token_mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.user_lp.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer,
+        ),
+        lp_out,
+    )?;
+    
+    // Record the deposit against the post-deposit pool value per LP
+    let sqrt_k_per_lp = get_sqrt_k_per_lp(
+        reserve_a.checked_add(amount_a).ok_or(AmmError::MathOverflow)?,
+        reserve_b.checked_add(amount_b).ok_or(AmmError::MathOverflow)?,
+        lp_supply.checked_add(lp_out).ok_or(AmmError::MathOverflow)?,
+    )?;
+    
+    let position = &mut ctx.accounts.position;
+    position.open(ctx.accounts.pool.key(), ctx.accounts.user.key(), ctx.bumps.position);
+    position.record_deposit(lp_out, amount_a, amount_b, sqrt_k_per_lp, Clock::get()?.unix_timestamp)?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddLiquidityExactLp<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump,
+        constraint = !pool.load()?.is_emergency_mode() @ AmmError::EmergencyMode
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.vault_a,
+        constraint = vault_a.mint == pool.load()?.token_mint_a @ AmmError::InvalidVault,
+        constraint = vault_a.owner == pool.key() @ AmmError::InvalidVault
+    )]
+    pub vault_a: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.vault_b,
+        constraint = vault_b.mint == pool.load()?.token_mint_b @ AmmError::InvalidVault,
+        constraint = vault_b.owner == pool.key() @ AmmError::InvalidVault
+    )]
+    pub vault_b: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.lp_mint,
+        constraint = lp_mint.mint_authority == COption::Some(pool.key()) @ AmmError::InvalidLpMint
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        constraint = user_token_a.mint == pool.load()?.token_mint_a,
+        constraint = user_token_a.owner == user.key()
+    )]
+    pub user_token_a: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = user_token_b.mint == pool.load()?.token_mint_b,
+        constraint = user_token_b.owner == user.key()
+    )]
+    pub user_token_b: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = user_lp.mint == pool.load()?.lp_mint,
+        constraint = user_lp.owner == user.key()
+    )]
+    pub user_lp: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    /// Tracks the deposit for fee and impermanent loss reporting
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = LpPosition::LEN,
+        seeds = [b"position", pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, LpPosition>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
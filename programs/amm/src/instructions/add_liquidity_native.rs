@@ -0,0 +1,213 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, MintTo};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_lang::solana_program::program_option::COption;
+use crate::state::{LpPosition, Pool};
+use crate::error::AmmError;
+use crate::math::{get_deposit_amounts, get_sqrt_k_per_lp};
+use crate::native_sol::{wrap_sol, unwrap_sol, WSOL_SEED};
+
+/// Same as `add_liquidity` for a pool with the native mint on one side, funding
+/// the SOL leg from the user's lamports through a temporary wSOL account.
+pub fn add_liquidity_native(
+    ctx: Context<AddLiquidityNative>,
+    amount_a_max: u64,
+    amount_b_max: u64,
+    amount_a_min: u64,
+    amount_b_min: u64,
+    min_lp_tokens: u64,
+    deadline: Option<i64>,
+) -> Result<()> {
+    if let Some(deadline) = deadline {
+        require!(Clock::get()?.unix_timestamp <= deadline, AmmError::DeadlineExceeded);
+    }
+    
+    require!(amount_a_max > 0 && amount_b_max > 0, AmmError::ZeroLiquidity);
+    
+    let pool = *ctx.accounts.pool.load()?;
+    
+    // Exactly one side is native SOL; the user supplies a token account for the other
+    let sol_is_a = pool.token_mint_a == native_mint::ID;
+    let token_mint = if sol_is_a {
+        pool.token_mint_b
+    } else {
+        require!(pool.token_mint_b == native_mint::ID, AmmError::InvalidMint);
+        pool.token_mint_a
+    };
+    require!(ctx.accounts.user_token.mint == token_mint, AmmError::InvalidMint);
+    
+    let reserve_a = ctx.accounts.vault_a.amount;
+    let reserve_b = ctx.accounts.vault_b.amount;
+    let lp_supply = ctx.accounts.lp_mint.supply;
+    
+    let (amount_a, amount_b, lp_tokens) = get_deposit_amounts(
+        amount_a_max,
+        amount_b_max,
+        amount_a_min,
+        amount_b_min,
+        reserve_a,
+        reserve_b,
+        lp_supply,
+    )?;
+    
+    require!(lp_tokens >= min_lp_tokens, AmmError::SlippageExceeded);
+    
+    let (sol_amount, sol_vault, token_amount, token_vault) = if sol_is_a {
+        (amount_a, &ctx.accounts.vault_a, amount_b, &ctx.accounts.vault_b)
+    } else {
+        (amount_b, &ctx.accounts.vault_b, amount_a, &ctx.accounts.vault_a)
+    };
+    
+    // Wrap the SOL leg and transfer it from the temporary account to its vault
+    wrap_sol(
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.temp_wsol.to_account_info(),
+        sol_amount,
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+    
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.temp_wsol.to_account_info(),
+                to: sol_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        sol_amount,
+    )?;
+    
+    // Transfer the token leg from user to vault
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token.to_account_info(),
+                to: token_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        token_amount,
+    )?;
+    
+    // Close the emptied temporary account to refund its rent
+    unwrap_sol(
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.temp_wsol.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+    
+    // Mint LP tokens to user
+    let seeds = &[
+        b"pool",
+        pool.token_mint_a.as_ref(),
+        pool.token_mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.user_lp.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer,
+        ),
+        lp_tokens,
+    )?;
+    
+    // Record the deposit against the post-deposit pool value per LP
+    let sqrt_k_per_lp = get_sqrt_k_per_lp(
+        reserve_a.checked_add(amount_a).ok_or(AmmError::MathOverflow)?,
+        reserve_b.checked_add(amount_b).ok_or(AmmError::MathOverflow)?,
+        lp_supply.checked_add(lp_tokens).ok_or(AmmError::MathOverflow)?,
+    )?;
+    
+    let position = &mut ctx.accounts.position;
+    position.open(ctx.accounts.pool.key(), ctx.accounts.user.key(), ctx.bumps.position);
+    position.record_deposit(lp_tokens, amount_a, amount_b, sqrt_k_per_lp, Clock::get()?.unix_timestamp)?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddLiquidityNative<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump,
+        constraint = !pool.load()?.is_emergency_mode() @ AmmError::EmergencyMode
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.vault_a,
+        constraint = vault_a.mint == pool.load()?.token_mint_a @ AmmError::InvalidVault,
+        constraint = vault_a.owner == pool.key() @ AmmError::InvalidVault
+    )]
+    pub vault_a: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.vault_b,
+        constraint = vault_b.mint == pool.load()?.token_mint_b @ AmmError::InvalidVault,
+        constraint = vault_b.owner == pool.key() @ AmmError::InvalidVault
+    )]
+    pub vault_b: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.lp_mint,
+        constraint = lp_mint.mint_authority == COption::Some(pool.key()) @ AmmError::InvalidLpMint
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    
+    /// User account for the non-native side of the pool
+    #[account(
+        mut,
+        constraint = user_token.owner == user.key()
+    )]
+    pub user_token: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = user_lp.mint == pool.load()?.lp_mint,
+        constraint = user_lp.owner == user.key()
+    )]
+    pub user_lp: Box<Account<'info, TokenAccount>>,
+    
+    /// Tracks the deposit for fee and impermanent loss reporting
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = LpPosition::LEN,
+        seeds = [b"position", pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, LpPosition>>,
+    
+    /// Temporary wSOL account, closed back to the user before the instruction ends
+    #[account(
+        init,
+        payer = user,
+        seeds = [WSOL_SEED, user.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = user
+    )]
+    pub temp_wsol: Box<Account<'info, TokenAccount>>,
+    
+    #[account(address = native_mint::ID)]
+    pub native_mint: Box<Account<'info, Mint>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, Transfer, MintTo};
+use anchor_lang::solana_program::program_option::COption;
+use crate::state::{LpPosition, Pool};
+use crate::error::AmmError;
+use crate::math::{get_single_sided_deposit_lp, get_sqrt_k_per_lp};
+
+pub fn add_liquidity_single(
+    ctx: Context<AddLiquiditySingle>,
+    token_in: Pubkey,
+    amount_in: u64,
+    min_lp_tokens: u64,
+) -> Result<()> {
+    require!(amount_in > 0, AmmError::ZeroLiquidity);
+    
+    let pool = *ctx.accounts.pool.load()?;
+    
+    // Determine which side of the pool is being deposited
+    let (vault_in, reserve_in, reserve_out) = if token_in == pool.token_mint_a {
+        (&ctx.accounts.vault_a, ctx.accounts.vault_a.amount, ctx.accounts.vault_b.amount)
+    } else if token_in == pool.token_mint_b {
+        (&ctx.accounts.vault_b, ctx.accounts.vault_b.amount, ctx.accounts.vault_a.amount)
+    } else {
+        return Err(AmmError::InvalidMint.into());
+    };
+    require!(ctx.accounts.user_token_in.mint == token_in, AmmError::InvalidMint);
+    
+    let lp_supply = ctx.accounts.lp_mint.supply;
+    
+    // Single-sided deposits need an existing price to swap against
+    require!(reserve_in > 0 && reserve_out > 0 && lp_supply > 0, AmmError::PoolNotReady);
+    
+    // Swap the optimal fraction of the deposit into the other token and
+    // deposit the remainder together with the swap output
+    let lp_tokens = get_single_sided_deposit_lp(amount_in, reserve_in, reserve_out, lp_supply, pool.fee_bps)?;
+    require!(lp_tokens >= min_lp_tokens, AmmError::SlippageExceeded);
+    
+    // The swap output is deposited straight back, so only the input side moves
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_in.to_account_info(),
+                to: vault_in.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount_in,
+    )?;
+    
+    // Mint LP tokens to user
+    let seeds = &[
+        b"pool",
+        pool.token_mint_a.as_ref(),
+        pool.token_mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.user_lp.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer,
+        ),
+        lp_tokens,
+    )?;
+    
+    // Record the deposit against the post-deposit pool value per LP; only
+    // the input side was paid in
+    let (amount_a, amount_b) = if token_in == pool.token_mint_a { (amount_in, 0) } else { (0, amount_in) };
+    let sqrt_k_per_lp = get_sqrt_k_per_lp(
+        ctx.accounts.vault_a.amount.checked_add(amount_a).ok_or(AmmError::MathOverflow)?,
+        ctx.accounts.vault_b.amount.checked_add(amount_b).ok_or(AmmError::MathOverflow)?,
+        lp_supply.checked_add(lp_tokens).ok_or(AmmError::MathOverflow)?,
+    )?;
+    
+    let position = &mut ctx.accounts.position;
+    position.open(ctx.accounts.pool.key(), ctx.accounts.user.key(), ctx.bumps.position);
+    position.record_deposit(lp_tokens, amount_a, amount_b, sqrt_k_per_lp, Clock::get()?.unix_timestamp)?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddLiquiditySingle<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump,
+        constraint = !pool.load()?.is_emergency_mode() @ AmmError::EmergencyMode
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.vault_a,
+        constraint = vault_a.mint == pool.load()?.token_mint_a @ AmmError::InvalidVault,
+        constraint = vault_a.owner == pool.key() @ AmmError::InvalidVault
+    )]
+    pub vault_a: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.vault_b,
+        constraint = vault_b.mint == pool.load()?.token_mint_b @ AmmError::InvalidVault,
+        constraint = vault_b.owner == pool.key() @ AmmError::InvalidVault
+    )]
+    pub vault_b: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.lp_mint,
+        constraint = lp_mint.mint_authority == COption::Some(pool.key()) @ AmmError::InvalidLpMint
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        constraint = user_token_in.owner == user.key()
+    )]
+    pub user_token_in: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = user_lp.mint == pool.load()?.lp_mint,
+        constraint = user_lp.owner == user.key()
+    )]
+    pub user_lp: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    /// Tracks the deposit for fee and impermanent loss reporting
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = LpPosition::LEN,
+        seeds = [b"position", pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, LpPosition>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
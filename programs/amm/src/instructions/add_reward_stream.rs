@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::{Config, Farm, RewardStream};
+use crate::error::AmmError;
+
+/// Register a new reward token on a farm in the first free stream slot.
+/// `funder` is the only authority allowed to fund and extend the stream.
+pub fn add_reward_stream(ctx: Context<AddRewardStream>, funder: Pubkey) -> Result<()> {
+    // Only admin can add reward streams
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.config.admin,
+        AmmError::Unauthorized
+    );
+    
+    let now = Clock::get()?.unix_timestamp;
+    let reward_mint = ctx.accounts.reward_mint.key();
+    let reward_vault = ctx.accounts.reward_vault.key();
+    let farm = &mut ctx.accounts.farm;
+    
+    let slot = farm
+        .reward_streams
+        .iter_mut()
+        .find(|stream| !stream.is_active())
+        .ok_or(AmmError::TooManyRewardStreams)?;
+    
+    *slot = RewardStream {
+        reward_mint,
+        reward_vault,
+        funder,
+        reward_per_second: 0,
+        acc_reward_per_share: 0,
+        last_update_ts: now,
+        end_ts: now,
+    };
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddRewardStream<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [b"farm", farm.pool.as_ref()],
+        bump = farm.bump
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+    
+    pub reward_mint: Box<Account<'info, Mint>>,
+    
+    /// One vault per reward mint, so the same token cannot back two streams
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"farm_reward", farm.key().as_ref(), reward_mint.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = farm
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
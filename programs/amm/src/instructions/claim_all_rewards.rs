@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{Farm, UserStake};
+use crate::error::AmmError;
+
+/// Claim every active reward stream in one instruction.
+///
+/// Remaining accounts hold one `[reward_vault, user_reward]` pair per active
+/// stream, in stream order. `user_reward` must already exist and be owned by
+/// the user.
+pub fn claim_all_rewards<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimAllRewards<'info>>) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+    farm.update_rewards(Clock::get()?.unix_timestamp)?;
+    
+    let stake = &mut ctx.accounts.user_stake;
+    stake.settle(farm)?;
+    stake.checkpoint(farm)?;
+    
+    let active_streams = farm.reward_streams.iter().filter(|stream| stream.is_active()).count();
+    require!(
+        ctx.remaining_accounts.len() == active_streams * 2,
+        AmmError::InvalidRewardStream
+    );
+    
+    // Farm PDA signer seeds
+    let farm_pool = farm.pool;
+    let seeds = &[
+        b"farm",
+        farm_pool.as_ref(),
+        &[farm.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    let mut accounts = ctx.remaining_accounts.chunks(2);
+    for (i, stream) in farm.reward_streams.iter().enumerate() {
+        if !stream.is_active() {
+            continue;
+        }
+        let pair = accounts.next().ok_or(AmmError::InvalidRewardStream)?;
+        let (reward_vault, user_reward) = (&pair[0], &pair[1]);
+    
+        require!(reward_vault.key() == stream.reward_vault, AmmError::InvalidVault);
+        let user_reward_account = Account::<TokenAccount>::try_from(user_reward)?;
+        require!(user_reward_account.mint == stream.reward_mint, AmmError::InvalidMint);
+        require!(user_reward_account.owner == ctx.accounts.user.key(), AmmError::Unauthorized);
+    
+        let rewards = stake.pending_rewards[i];
+        if rewards == 0 {
+            continue;
+        }
+        stake.pending_rewards[i] = 0;
+    
+        // Transfer rewards from stream vault to user
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: reward_vault.clone(),
+                    to: user_reward.clone(),
+                    authority: farm.to_account_info(),
+                },
+                signer,
+            ),
+            rewards,
+        )?;
+    }
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimAllRewards<'info> {
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"farm", farm.pool.as_ref()],
+        bump = farm.bump
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+    
+    #[account(
+        mut,
+        seeds = [b"stake", farm.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+    
+    pub token_program: Program<'info, Token>,
+}
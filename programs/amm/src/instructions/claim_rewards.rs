@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::state::{Farm, UserStake};
+use crate::error::AmmError;
+
+pub fn claim_rewards(ctx: Context<ClaimRewards>, stream_index: u8) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+    farm.update_rewards(Clock::get()?.unix_timestamp)?;
+    
+    let stream = farm.stream(stream_index)?;
+    require!(ctx.accounts.reward_mint.key() == stream.reward_mint, AmmError::InvalidMint);
+    require!(ctx.accounts.reward_vault.key() == stream.reward_vault, AmmError::InvalidVault);
+    
+    let stake = &mut ctx.accounts.user_stake;
+    stake.settle(farm)?;
+    stake.checkpoint(farm)?;
+    
+    let rewards = stake.pending_rewards[stream_index as usize];
+    if rewards == 0 {
+        return Ok(());
+    }
+    stake.pending_rewards[stream_index as usize] = 0;
+    
+    // Farm PDA signer seeds
+    let farm_pool = farm.pool;
+    let seeds = &[
+        b"farm",
+        farm_pool.as_ref(),
+        &[farm.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    // Transfer rewards from stream vault to user
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.reward_vault.to_account_info(),
+                to: ctx.accounts.user_reward.to_account_info(),
+                authority: ctx.accounts.farm.to_account_info(),
+            },
+            signer,
+        ),
+        rewards,
+    )?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"farm", farm.pool.as_ref()],
+        bump = farm.bump
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+    
+    #[account(
+        mut,
+        seeds = [b"stake", farm.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+    
+    pub reward_mint: Box<Account<'info, Mint>>,
+    
+    #[account(mut)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = reward_mint,
+        associated_token::authority = user
+    )]
+    pub user_reward: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Mint, Token, TokenAccount, Transfer};
+use crate::state::{Pool, Config};
+use crate::error::AmmError;
+
+/// Retire a pool with no LP supply, closing both vaults and the pool account and
+/// returning their rent to `recipient`. Whatever the vaults still hold belongs to
+/// no LP, so it is swept to the admin's `destination_a` and `destination_b`
+/// first; otherwise a donated dust amount could keep the pool open forever. The
+/// LP mint is left in place since SPL Token mints cannot be closed.
+pub fn close_pool(ctx: Context<ClosePool>) -> Result<()> {
+    // Only admin can close pools
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.config.admin,
+        AmmError::Unauthorized
+    );
+    
+    require!(ctx.accounts.lp_mint.supply == 0, AmmError::PoolNotEmpty);
+    
+    let pool = *ctx.accounts.pool.load()?;
+    
+    // PDA signer seeds
+    let seeds = &[
+        b"pool",
+        pool.token_mint_a.as_ref(),
+        pool.token_mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    // Sweep leftover balances to the admin
+    for (vault, destination) in [
+        (&ctx.accounts.vault_a, &ctx.accounts.destination_a),
+        (&ctx.accounts.vault_b, &ctx.accounts.destination_b),
+    ] {
+        if vault.amount > 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: vault.to_account_info(),
+                        to: destination.to_account_info(),
+                        authority: ctx.accounts.pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                vault.amount,
+            )?;
+        }
+    }
+    
+    // Close vault A
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vault_a.to_account_info(),
+            destination: ctx.accounts.recipient.to_account_info(),
+            authority: ctx.accounts.pool.to_account_info(),
+        },
+        signer,
+    ))?;
+    
+    // Close vault B
+    token::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.vault_b.to_account_info(),
+            destination: ctx.accounts.recipient.to_account_info(),
+            authority: ctx.accounts.pool.to_account_info(),
+        },
+        signer,
+    ))?;
+    
+    // The pool account itself is closed by the `close` constraint
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClosePool<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump,
+        close = recipient
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.vault_a @ AmmError::InvalidVault
+    )]
+    pub vault_a: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.vault_b @ AmmError::InvalidVault
+    )]
+    pub vault_b: Box<Account<'info, TokenAccount>>,
+    
+    #[account(address = pool.load()?.lp_mint @ AmmError::InvalidLpMint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    
+    /// Receives the token A left in `vault_a`; must belong to the admin
+    #[account(
+        mut,
+        constraint = destination_a.mint == pool.load()?.token_mint_a @ AmmError::InvalidMint,
+        constraint = destination_a.owner == config.admin @ AmmError::Unauthorized
+    )]
+    pub destination_a: Box<Account<'info, TokenAccount>>,
+    
+    /// Receives the token B left in `vault_b`; must belong to the admin
+    #[account(
+        mut,
+        constraint = destination_b.mint == pool.load()?.token_mint_b @ AmmError::InvalidMint,
+        constraint = destination_b.owner == config.admin @ AmmError::Unauthorized
+    )]
+    pub destination_b: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: Any account may receive the reclaimed rent
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Transfer};
+use crate::error::AmmError;
+use super::UnstakeLp;
+
+/// Withdraw the whole stake without settling rewards, forfeiting everything
+/// earned and not yet claimed. A way out for stakers whose reward accounting
+/// no longer fits its types, which would otherwise lock their LP in the farm.
+pub fn emergency_unstake_lp(ctx: Context<UnstakeLp>) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+    let stake = &mut ctx.accounts.user_stake;
+    let amount = stake.amount;
+    require!(amount > 0, AmmError::ZeroLiquidity);
+    
+    // Accrue each stream that still can, so the remaining stakers are not paid
+    // this stake's share of the time since the last update; a stream whose
+    // accumulator overflows is left as it is
+    let now = Clock::get()?.unix_timestamp;
+    let total_staked = farm.total_staked;
+    for stream in farm.reward_streams.iter_mut().filter(|stream| stream.is_active()) {
+        stream.update_rewards(now, total_staked).ok();
+    }
+    
+    // Forfeited rewards stay in the reward vaults
+    stake.amount = 0;
+    stake.reward_debts = Default::default();
+    stake.pending_rewards = Default::default();
+    
+    farm.total_staked = farm.total_staked.checked_sub(amount).ok_or(AmmError::MathOverflow)?;
+    
+    // Farm PDA signer seeds
+    let farm_pool = farm.pool;
+    let seeds = &[
+        b"farm",
+        farm_pool.as_ref(),
+        &[farm.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    // Transfer the whole stake from farm vault to user
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.lp_vault.to_account_info(),
+                to: ctx.accounts.user_lp.to_account_info(),
+                authority: ctx.accounts.farm.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+    
+    Ok(())
+}
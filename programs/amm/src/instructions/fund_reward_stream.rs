@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::Farm;
+use crate::error::AmmError;
+
+/// Add rewards to a stream and (re)start its reward period. Rewards still
+/// unemitted from the current period are rolled into the new one, so this both
+/// funds and extends a running stream.
+pub fn fund_reward_stream(
+    ctx: Context<FundRewardStream>,
+    stream_index: u8,
+    amount: u64,
+    duration: i64,
+) -> Result<()> {
+    require!(duration > 0, AmmError::InvalidRewardDuration);
+    
+    let now = Clock::get()?.unix_timestamp;
+    let farm = &mut ctx.accounts.farm;
+    farm.update_rewards(now)?;
+    
+    let stream = *farm.stream(stream_index)?;
+    
+    // Only the stream's funder can fund it
+    require!(ctx.accounts.funder.key() == stream.funder, AmmError::Unauthorized);
+    require!(ctx.accounts.reward_vault.key() == stream.reward_vault, AmmError::InvalidVault);
+    require!(ctx.accounts.funder_reward.mint == stream.reward_mint, AmmError::InvalidMint);
+    
+    // Carry over whatever the current period has not emitted yet
+    let remaining = if stream.end_ts > now {
+        (stream.reward_per_second as u128)
+            .checked_mul((stream.end_ts - now) as u128)
+            .ok_or(AmmError::MathOverflow)?
+    } else {
+        0
+    };
+    
+    let reward_per_second = remaining
+        .checked_add(amount as u128)
+        .ok_or(AmmError::MathOverflow)?
+        .checked_div(duration as u128)
+        .ok_or(AmmError::MathOverflow)?;
+    
+    let stream = &mut farm.reward_streams[stream_index as usize];
+    stream.reward_per_second = u64::try_from(reward_per_second).map_err(|_| AmmError::MathOverflow)?;
+    stream.last_update_ts = now;
+    stream.end_ts = now.checked_add(duration).ok_or(AmmError::MathOverflow)?;
+    
+    if amount > 0 {
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder_reward.to_account_info(),
+                    to: ctx.accounts.reward_vault.to_account_info(),
+                    authority: ctx.accounts.funder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    }
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FundRewardStream<'info> {
+    pub funder: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"farm", farm.pool.as_ref()],
+        bump = farm.bump
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+    
+    #[account(mut)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = funder_reward.owner == funder.key()
+    )]
+    pub funder_reward: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
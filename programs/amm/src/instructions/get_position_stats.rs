@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use crate::state::{LpPosition, Pool};
+use crate::error::AmmError;
+use crate::math::{get_fees_earned, get_impermanent_loss_bps, get_sqrt_k_per_lp, quote};
+
+/// Fee income and impermanent loss of an LP position at current reserves
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct PositionStats {
+    /// LP tokens attributed to the position
+    pub lp_amount: u64,
+    /// Token A the position redeems for now, fees included
+    pub amount_a: u64,
+    /// Token B the position redeems for now, fees included
+    pub amount_b: u64,
+    /// Part of `amount_a` earned from swap fees
+    pub fees_a: u64,
+    /// Part of `amount_b` earned from swap fees
+    pub fees_b: u64,
+    /// Growth in `sqrt(k)` per LP token since entry, scaled by `FEE_GROWTH_PRECISION`
+    pub fee_growth: u128,
+    /// Deposited tokens valued at the current price, in token B
+    pub hold_value_b: u64,
+    /// Position excluding fees valued at the current price, in token B
+    pub position_value_b: u64,
+    /// Impermanent loss in basis points of `hold_value_b`
+    pub impermanent_loss_bps: i64,
+    /// Seconds since the position was opened
+    pub age_seconds: i64,
+}
+
+/// Read-only: report a position's accrued fees and impermanent loss.
+/// The result is returned through the transaction's return data.
+pub fn get_position_stats(ctx: Context<GetPositionStats>) -> Result<PositionStats> {
+    let position = &ctx.accounts.position;
+    let reserve_a = ctx.accounts.vault_a.amount;
+    let reserve_b = ctx.accounts.vault_b.amount;
+    let lp_supply = ctx.accounts.lp_mint.supply;
+    
+    let mut stats = PositionStats {
+        lp_amount: position.lp_amount,
+        age_seconds: Clock::get()?.unix_timestamp.saturating_sub(position.opened_at),
+        ..Default::default()
+    };
+    
+    // Nothing to value without a position or a price
+    if position.lp_amount == 0 || lp_supply == 0 || reserve_a == 0 {
+        return Ok(stats);
+    }
+    
+    // LP burned outside add_liquidity/remove_liquidity can leave the position above supply
+    let lp_amount = position.lp_amount.min(lp_supply);
+    stats.amount_a = quote(lp_amount, lp_supply, reserve_a)?;
+    stats.amount_b = quote(lp_amount, lp_supply, reserve_b)?;
+    
+    let sqrt_k_per_lp = get_sqrt_k_per_lp(reserve_a, reserve_b, lp_supply)?;
+    stats.fee_growth = sqrt_k_per_lp.saturating_sub(position.entry_sqrt_k_per_lp);
+    stats.fees_a = get_fees_earned(stats.amount_a, position.entry_sqrt_k_per_lp, sqrt_k_per_lp)?;
+    stats.fees_b = get_fees_earned(stats.amount_b, position.entry_sqrt_k_per_lp, sqrt_k_per_lp)?;
+    
+    // Compare against simply holding the deposited tokens, both at the current price
+    stats.hold_value_b = quote(position.entry_amount_a, reserve_a, reserve_b)?
+        .checked_add(position.entry_amount_b)
+        .ok_or(AmmError::MathOverflow)?;
+    stats.position_value_b = quote(stats.amount_a - stats.fees_a, reserve_a, reserve_b)?
+        .checked_add(stats.amount_b - stats.fees_b)
+        .ok_or(AmmError::MathOverflow)?;
+    stats.impermanent_loss_bps = get_impermanent_loss_bps(stats.hold_value_b, stats.position_value_b)?;
+    
+    Ok(stats)
+}
+
+#[derive(Accounts)]
+pub struct GetPositionStats<'info> {
+    #[account(
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    
+    #[account(
+        address = pool.load()?.vault_a,
+        constraint = vault_a.mint == pool.load()?.token_mint_a @ AmmError::InvalidVault
+    )]
+    pub vault_a: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        address = pool.load()?.vault_b,
+        constraint = vault_b.mint == pool.load()?.token_mint_b @ AmmError::InvalidVault
+    )]
+    pub vault_b: Box<Account<'info, TokenAccount>>,
+    
+    #[account(address = pool.load()?.lp_mint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        seeds = [b"position", pool.key().as_ref(), position.owner.as_ref()],
+        bump = position.bump
+    )]
+    pub position: Box<Account<'info, LpPosition>>,
+}
@@ -1,11 +1,13 @@
 use anchor_lang::prelude::*;
-use crate::state::Config;
+use crate::state::{Config, CONFIG_VERSION};
 
 pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
     let config = &mut ctx.accounts.config;
     
     config.admin = ctx.accounts.admin.key();
     config.bump = ctx.bumps.config;
+    config.max_referral_bps = 0;
+    config.version = CONFIG_VERSION;
     
     Ok(())
 }
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::state::{Pool, Config, Farm, RewardStream, MAX_REWARD_STREAMS};
+use crate::error::AmmError;
+
+pub fn initialize_farm(ctx: Context<InitializeFarm>) -> Result<()> {
+    // Only admin can create farms
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.config.admin,
+        AmmError::Unauthorized
+    );
+    
+    let farm = &mut ctx.accounts.farm;
+    
+    farm.pool = ctx.accounts.pool.key();
+    farm.lp_mint = ctx.accounts.lp_mint.key();
+    farm.lp_vault = ctx.accounts.lp_vault.key();
+    farm.total_staked = 0;
+    farm.bump = ctx.bumps.farm;
+    farm.reward_streams = [RewardStream::default(); MAX_REWARD_STREAMS];
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeFarm<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = Farm::LEN,
+        seeds = [b"farm", pool.key().as_ref()],
+        bump
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+    
+    #[account(address = pool.load()?.lp_mint @ AmmError::InvalidLpMint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"farm_lp", farm.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = farm
+    )]
+    pub lp_vault: Box<Account<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
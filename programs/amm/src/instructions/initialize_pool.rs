@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use anchor_lang::solana_program::program_option::COption;
-use crate::state::{Pool, Config};
+use crate::state::{Pool, Config, POOL_VERSION};
 use crate::error::AmmError;
 
 pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
@@ -11,7 +11,7 @@ pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
         AmmError::Unauthorized
     );
     
-    let pool = &mut ctx.accounts.pool;
+    let mut pool = ctx.accounts.pool.load_init()?;
     
     require!(
         ctx.accounts.token_mint_a.key() != ctx.accounts.token_mint_b.key(),
@@ -30,6 +30,8 @@ pub fn initialize_pool(ctx: Context<InitializePool>) -> Result<()> {
     pool.lp_mint = ctx.accounts.lp_mint.key();
     pool.fee_bps = 30;
     pool.bump = ctx.bumps.pool;
+    pool.emergency_mode = 0;
+    pool.version = POOL_VERSION;
     
     Ok(())
 }
@@ -50,11 +52,11 @@ pub struct InitializePool<'info> {
     #[account(
         init,
         payer = payer,
-        space = 8 + Pool::LEN,
+        space = Pool::LEN,
         seeds = [b"pool", token_mint_a.key().as_ref(), token_mint_b.key().as_ref()],
         bump
     )]
-    pub pool: Account<'info, Pool>,
+    pub pool: AccountLoader<'info, Pool>,
     
     /// CHECK: We validate the mint manually
     pub token_mint_a: UncheckedAccount<'info>,
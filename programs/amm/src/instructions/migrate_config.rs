@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::{Config, CONFIG_VERSION};
+use crate::error::AmmError;
+use crate::migration::grow_account;
+
+/// Bring the config account to the current layout. Older configs are too short
+/// to deserialize as `Config`, so the account is grown first and the new fields
+/// read as zero.
+pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+    let info = ctx.accounts.config.to_account_info();
+    grow_account(
+        info.clone(),
+        ctx.accounts.admin.to_account_info(),
+        8 + Config::LEN,
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+    
+    let mut config = Config::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    
+    // Only admin can migrate accounts
+    require!(
+        ctx.accounts.admin.key() == config.admin,
+        AmmError::Unauthorized
+    );
+    
+    require!(config.version < CONFIG_VERSION, AmmError::AlreadyMigrated);
+    
+    config.version = CONFIG_VERSION;
+    let mut data = info.try_borrow_mut_data()?;
+    config.try_serialize(&mut &mut data[..])?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    /// CHECK: Deserialized in the handler once it has been grown to the current size
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump,
+        owner = crate::ID
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::state::{Pool, Config, POOL_VERSION};
+use crate::error::AmmError;
+use crate::migration::grow_account;
+
+/// Bring a pool to the current layout. Pools written before the zero-copy
+/// layout are shorter than `Pool`, so the account is grown first; the bytes
+/// they already hold line up with the zero-copy fields.
+pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+    // Only admin can migrate accounts
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.config.admin,
+        AmmError::Unauthorized
+    );
+    
+    let info = ctx.accounts.pool.to_account_info();
+    grow_account(
+        info.clone(),
+        ctx.accounts.admin.to_account_info(),
+        Pool::LEN,
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+    
+    let mut data = info.try_borrow_mut_data()?;
+    require!(
+        data[..8] == Pool::DISCRIMINATOR,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    let pool: &mut Pool = bytemuck::from_bytes_mut(&mut data[8..Pool::LEN]);
+    
+    require!(pool.version < POOL_VERSION, AmmError::AlreadyMigrated);
+    
+    pool.version = POOL_VERSION;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    /// CHECK: Loaded in the handler once it has been grown to the current size
+    #[account(
+        mut,
+        owner = crate::ID
+    )]
+    pub pool: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
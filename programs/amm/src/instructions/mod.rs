@@ -1,12 +1,56 @@
 pub mod initialize_config;
 pub mod initialize_pool;
+pub mod close_pool;
 pub mod add_liquidity;
+pub mod add_liquidity_single;
+pub mod add_liquidity_exact_lp;
+pub mod add_liquidity_native;
 pub mod remove_liquidity;
+pub mod get_position_stats;
+pub mod remove_liquidity_single;
+pub mod remove_liquidity_exact_out;
+pub mod remove_liquidity_native;
 pub mod swap;
+pub mod swap_native;
+pub mod set_max_referral_bps;
+pub mod set_emergency_mode;
+pub mod recover_tokens;
+pub mod initialize_farm;
+pub mod add_reward_stream;
+pub mod fund_reward_stream;
+pub mod stake_lp;
+pub mod unstake_lp;
+pub mod emergency_unstake_lp;
+pub mod claim_rewards;
+pub mod claim_all_rewards;
+pub mod migrate_config;
+pub mod migrate_pool;
 
 pub use initialize_config::*;
 pub use initialize_pool::*;
+pub use close_pool::*;
 pub use add_liquidity::*;
+pub use add_liquidity_single::*;
+pub use add_liquidity_exact_lp::*;
+pub use add_liquidity_native::*;
 pub use remove_liquidity::*;
+pub use get_position_stats::*;
+pub use remove_liquidity_single::*;
+pub use remove_liquidity_exact_out::*;
+pub use remove_liquidity_native::*;
 pub use swap::*;
+pub use swap_native::*;
+pub use set_max_referral_bps::*;
+pub use set_emergency_mode::*;
+pub use recover_tokens::*;
+pub use initialize_farm::*;
+pub use add_reward_stream::*;
+pub use fund_reward_stream::*;
+pub use stake_lp::*;
+pub use unstake_lp::*;
+pub use emergency_unstake_lp::*;
+pub use claim_rewards::*;
+pub use claim_all_rewards::*;
+pub use migrate_config::*;
+pub use migrate_pool::*;
 
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{Pool, Config};
+use crate::error::AmmError;
+
+/// Move tokens that were mistakenly sent to a pool-owned token account back out.
+/// Pool reserves and LP tokens can never be recovered this way.
+pub fn recover_tokens(ctx: Context<RecoverTokens>, amount: u64) -> Result<()> {
+    // Only admin can recover tokens
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.config.admin,
+        AmmError::Unauthorized
+    );
+    
+    require!(amount > 0, AmmError::ZeroLiquidity);
+    
+    let pool = *ctx.accounts.pool.load()?;
+    
+    // PDA signer seeds
+    let seeds = &[
+        b"pool",
+        pool.token_mint_a.as_ref(),
+        pool.token_mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.source.to_account_info(),
+                to: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RecoverTokens<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    
+    /// Pool-owned token account holding the stray tokens
+    #[account(
+        mut,
+        constraint = source.owner == pool.key() @ AmmError::InvalidVault,
+        constraint = source.mint != pool.load()?.token_mint_a @ AmmError::CannotRecoverPoolTokens,
+        constraint = source.mint != pool.load()?.token_mint_b @ AmmError::CannotRecoverPoolTokens,
+        constraint = source.mint != pool.load()?.lp_mint @ AmmError::CannotRecoverPoolTokens
+    )]
+    pub source: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = destination.mint == source.mint @ AmmError::InvalidMint
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
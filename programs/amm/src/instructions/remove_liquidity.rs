@@ -1,41 +1,44 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, Mint, Token, Transfer, Burn};
 use anchor_lang::solana_program::program_option::COption;
-use crate::state::Pool;
+use crate::state::{LpPosition, Pool};
 use crate::error::AmmError;
+use crate::math::get_withdraw_amounts;
 
 pub fn remove_liquidity(
     ctx: Context<RemoveLiquidity>,
     lp_amount: u64,
+    min_amount_a: u64,
+    min_amount_b: u64,
+    deadline: Option<i64>,
 ) -> Result<()> {
+    if let Some(deadline) = deadline {
+        require!(Clock::get()?.unix_timestamp <= deadline, AmmError::DeadlineExceeded);
+    }
+    
     require!(lp_amount > 0, AmmError::ZeroLiquidity);
     
-    let pool = &ctx.accounts.pool;
+    let pool = *ctx.accounts.pool.load()?;
+    let user_token_a = match (&ctx.accounts.user_token_a, &ctx.accounts.user_token_a_ata) {
+        (Some(account), None) | (None, Some(account)) => account,
+        _ => return Err(AmmError::InvalidOutputAccount.into()),
+    };
+    let user_token_b = match (&ctx.accounts.user_token_b, &ctx.accounts.user_token_b_ata) {
+        (Some(account), None) | (None, Some(account)) => account,
+        _ => return Err(AmmError::InvalidOutputAccount.into()),
+    };
     let reserve_a = ctx.accounts.vault_a.amount;
     let reserve_b = ctx.accounts.vault_b.amount;
     let lp_supply = ctx.accounts.lp_mint.supply;
     
-    require!(lp_supply > 0, AmmError::InsufficientLiquidity);
-    require!(lp_amount <= lp_supply, AmmError::InsufficientLiquidity);
-    
     // Calculate proportional amounts
-    let amount_a = (lp_amount as u128)
-        .checked_mul(reserve_a as u128)
-        .ok_or(AmmError::MathOverflow)?
-        .checked_div(lp_supply as u128)
-        .ok_or(AmmError::MathOverflow)?;
-    
-    let amount_b = (lp_amount as u128)
-        .checked_mul(reserve_b as u128)
-        .ok_or(AmmError::MathOverflow)?
-        .checked_div(lp_supply as u128)
-        .ok_or(AmmError::MathOverflow)?;
-    
-    let amount_a = u64::try_from(amount_a).map_err(|_| AmmError::MathOverflow)?;
-    let amount_b = u64::try_from(amount_b).map_err(|_| AmmError::MathOverflow)?;
+    let (amount_a, amount_b) = get_withdraw_amounts(lp_amount, reserve_a, reserve_b, lp_supply)?;
     
-    require!(amount_a > 0 && amount_b > 0, AmmError::InsufficientLiquidity);
-    require!(amount_a <= reserve_a && amount_b <= reserve_b, AmmError::InsufficientLiquidity);
+    require!(
+        amount_a >= min_amount_a && amount_b >= min_amount_b,
+        AmmError::SlippageExceeded
+    );
     
     // Burn LP tokens from user
     token::burn(
@@ -65,7 +68,7 @@ pub fn remove_liquidity(
             ctx.accounts.token_program.to_account_info(),
             Transfer {
                 from: ctx.accounts.vault_a.to_account_info(),
-                to: ctx.accounts.user_token_a.to_account_info(),
+                to: user_token_a.to_account_info(),
                 authority: ctx.accounts.pool.to_account_info(),
             },
             signer,
@@ -79,7 +82,7 @@ pub fn remove_liquidity(
             ctx.accounts.token_program.to_account_info(),
             Transfer {
                 from: ctx.accounts.vault_b.to_account_info(),
-                to: ctx.accounts.user_token_b.to_account_info(),
+                to: user_token_b.to_account_info(),
                 authority: ctx.accounts.pool.to_account_info(),
             },
             signer,
@@ -87,6 +90,8 @@ pub fn remove_liquidity(
         amount_b,
     )?;
     
+    LpPosition::record_withdrawal_if_open(&ctx.accounts.position, lp_amount, Clock::get()?.unix_timestamp)?;
+    
     Ok(())
 }
 
@@ -97,61 +102,91 @@ pub struct RemoveLiquidity<'info> {
     
     #[account(
         mut,
-        seeds = [b"pool", pool.token_mint_a.as_ref(), pool.token_mint_b.as_ref()],
-        bump = pool.bump
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump
     )]
-    pub pool: Box<Account<'info, Pool>>,
+    pub pool: AccountLoader<'info, Pool>,
     
-    #[account(address = pool.token_mint_a)]
+    #[account(address = pool.load()?.token_mint_a)]
     pub token_mint_a: Box<Account<'info, Mint>>,
     
-    #[account(address = pool.token_mint_b)]
+    #[account(address = pool.load()?.token_mint_b)]
     pub token_mint_b: Box<Account<'info, Mint>>,
     
     #[account(
         mut,
-        address = pool.vault_a,
-        constraint = vault_a.mint == pool.token_mint_a @ AmmError::InvalidVault,
+        address = pool.load()?.vault_a,
+        constraint = vault_a.mint == pool.load()?.token_mint_a @ AmmError::InvalidVault,
         constraint = vault_a.owner == pool.key() @ AmmError::InvalidVault
     )]
     pub vault_a: Box<Account<'info, anchor_spl::token::TokenAccount>>,
     
     #[account(
         mut,
-        address = pool.vault_b,
-        constraint = vault_b.mint == pool.token_mint_b @ AmmError::InvalidVault,
+        address = pool.load()?.vault_b,
+        constraint = vault_b.mint == pool.load()?.token_mint_b @ AmmError::InvalidVault,
         constraint = vault_b.owner == pool.key() @ AmmError::InvalidVault
     )]
     pub vault_b: Box<Account<'info, anchor_spl::token::TokenAccount>>,
     
     #[account(
         mut,
-        address = pool.lp_mint,
+        address = pool.load()?.lp_mint,
         constraint = lp_mint.mint_authority == COption::Some(pool.key()) @ AmmError::InvalidLpMint
     )]
     pub lp_mint: Box<Account<'info, Mint>>,
     
     #[account(
         mut,
-        constraint = user_lp.mint == pool.lp_mint,
+        constraint = user_lp.mint == pool.load()?.lp_mint,
         constraint = user_lp.owner == user.key()
     )]
     pub user_lp: Box<Account<'info, anchor_spl::token::TokenAccount>>,
     
+    /// Existing account receiving token A; pass this or `user_token_a_ata`
     #[account(
         mut,
-        constraint = user_token_a.mint == pool.token_mint_a,
+        constraint = user_token_a.mint == pool.load()?.token_mint_a,
         constraint = user_token_a.owner == user.key()
     )]
-    pub user_token_a: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    pub user_token_a: Option<Box<Account<'info, anchor_spl::token::TokenAccount>>>,
     
+    /// Existing account receiving token B; pass this or `user_token_b_ata`
     #[account(
         mut,
-        constraint = user_token_b.mint == pool.token_mint_b,
+        constraint = user_token_b.mint == pool.load()?.token_mint_b,
         constraint = user_token_b.owner == user.key()
     )]
-    pub user_token_b: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    pub user_token_b: Option<Box<Account<'info, anchor_spl::token::TokenAccount>>>,
+    
+    /// The user's associated token accounts for the pool tokens, created on
+    /// demand so withdrawals need no setup instruction
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = user
+    )]
+    pub user_token_a_ata: Option<Box<Account<'info, anchor_spl::token::TokenAccount>>>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = token_mint_b,
+        associated_token::authority = user
+    )]
+    pub user_token_b_ata: Option<Box<Account<'info, anchor_spl::token::TokenAccount>>>,
+    
+    /// CHECK: The user's position PDA, updated in the handler if it exists
+    #[account(
+        mut,
+        seeds = [b"position", pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: UncheckedAccount<'info>,
     
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
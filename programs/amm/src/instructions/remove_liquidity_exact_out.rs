@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, Transfer, Burn};
+use anchor_lang::solana_program::program_option::COption;
+use crate::state::{LpPosition, Pool};
+use crate::error::AmmError;
+use crate::math::get_lp_for_exact_withdrawal;
+
+pub fn remove_liquidity_exact_out(
+    ctx: Context<RemoveLiquidityExactOut>,
+    amount_a: u64,
+    amount_b: u64,
+    max_lp_in: u64,
+) -> Result<()> {
+    require!(amount_a > 0 || amount_b > 0, AmmError::ZeroLiquidity);
+    
+    let pool = *ctx.accounts.pool.load()?;
+    let reserve_a = ctx.accounts.vault_a.amount;
+    let reserve_b = ctx.accounts.vault_b.amount;
+    let lp_supply = ctx.accounts.lp_mint.supply;
+    
+    // Minimal LP to burn, with the swap fee charged on any imbalance
+    let lp_amount = get_lp_for_exact_withdrawal(
+        amount_a,
+        amount_b,
+        reserve_a,
+        reserve_b,
+        lp_supply,
+        pool.fee_bps,
+    )?;
+    
+    require!(lp_amount <= max_lp_in, AmmError::SlippageExceeded);
+    
+    // Burn LP tokens from user
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.user_lp.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        lp_amount,
+    )?;
+    
+    // PDA signer seeds
+    let seeds = &[
+        b"pool",
+        pool.token_mint_a.as_ref(),
+        pool.token_mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    // Transfer token A from vault to user
+    if amount_a > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_a.to_account_info(),
+                    to: ctx.accounts.user_token_a.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount_a,
+        )?;
+    }
+    
+    // Transfer token B from vault to user
+    if amount_b > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_b.to_account_info(),
+                    to: ctx.accounts.user_token_b.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount_b,
+        )?;
+    }
+    
+    LpPosition::record_withdrawal_if_open(&ctx.accounts.position, lp_amount, Clock::get()?.unix_timestamp)?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquidityExactOut<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump,
+        constraint = !pool.load()?.is_emergency_mode() @ AmmError::EmergencyMode
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    
+    #[account(address = pool.load()?.token_mint_a)]
+    pub token_mint_a: Box<Account<'info, Mint>>,
+    
+    #[account(address = pool.load()?.token_mint_b)]
+    pub token_mint_b: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.vault_a,
+        constraint = vault_a.mint == pool.load()?.token_mint_a @ AmmError::InvalidVault,
+        constraint = vault_a.owner == pool.key() @ AmmError::InvalidVault
+    )]
+    pub vault_a: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.vault_b,
+        constraint = vault_b.mint == pool.load()?.token_mint_b @ AmmError::InvalidVault,
+        constraint = vault_b.owner == pool.key() @ AmmError::InvalidVault
+    )]
+    pub vault_b: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.lp_mint,
+        constraint = lp_mint.mint_authority == COption::Some(pool.key()) @ AmmError::InvalidLpMint
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        constraint = user_lp.mint == pool.load()?.lp_mint,
+        constraint = user_lp.owner == user.key()
+    )]
+    pub user_lp: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    /// CHECK: The user's position PDA, updated in the handler if it exists
+    #[account(
+        mut,
+        seeds = [b"position", pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = user_token_a.mint == pool.load()?.token_mint_a,
+        constraint = user_token_a.owner == user.key()
+    )]
+    pub user_token_a: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = user_token_b.mint == pool.load()?.token_mint_b,
+        constraint = user_token_b.owner == user.key()
+    )]
+    pub user_token_b: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, Burn};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_lang::solana_program::program_option::COption;
+use crate::state::{LpPosition, Pool};
+use crate::error::AmmError;
+use crate::math::get_withdraw_amounts;
+use crate::native_sol::{unwrap_sol, WSOL_SEED};
+
+/// Same as `remove_liquidity` for a pool with the native mint on one side,
+/// paying the SOL leg out as lamports through a temporary wSOL account.
+pub fn remove_liquidity_native(
+    ctx: Context<RemoveLiquidityNative>,
+    lp_amount: u64,
+    min_amount_a: u64,
+    min_amount_b: u64,
+    deadline: Option<i64>,
+) -> Result<()> {
+    if let Some(deadline) = deadline {
+        require!(Clock::get()?.unix_timestamp <= deadline, AmmError::DeadlineExceeded);
+    }
+    
+    require!(lp_amount > 0, AmmError::ZeroLiquidity);
+    
+    let pool = *ctx.accounts.pool.load()?;
+    
+    // Exactly one side is native SOL; the user supplies a token account for the other
+    let sol_is_a = pool.token_mint_a == native_mint::ID;
+    let token_mint = if sol_is_a {
+        pool.token_mint_b
+    } else {
+        require!(pool.token_mint_b == native_mint::ID, AmmError::InvalidMint);
+        pool.token_mint_a
+    };
+    require!(ctx.accounts.user_token.mint == token_mint, AmmError::InvalidMint);
+    
+    let reserve_a = ctx.accounts.vault_a.amount;
+    let reserve_b = ctx.accounts.vault_b.amount;
+    let lp_supply = ctx.accounts.lp_mint.supply;
+    
+    // Calculate proportional amounts
+    let (amount_a, amount_b) = get_withdraw_amounts(lp_amount, reserve_a, reserve_b, lp_supply)?;
+    
+    require!(
+        amount_a >= min_amount_a && amount_b >= min_amount_b,
+        AmmError::SlippageExceeded
+    );
+    
+    // Burn LP tokens from user
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.user_lp.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        lp_amount,
+    )?;
+    
+    // PDA signer seeds
+    let seeds = &[
+        b"pool",
+        pool.token_mint_a.as_ref(),
+        pool.token_mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    // SOL leg goes to the temporary account, the token leg straight to the user
+    let (user_a, user_b) = if sol_is_a {
+        (ctx.accounts.temp_wsol.to_account_info(), ctx.accounts.user_token.to_account_info())
+    } else {
+        (ctx.accounts.user_token.to_account_info(), ctx.accounts.temp_wsol.to_account_info())
+    };
+    
+    // Transfer token A from vault to user
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_a.to_account_info(),
+                to: user_a,
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount_a,
+    )?;
+    
+    // Transfer token B from vault to user
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_b.to_account_info(),
+                to: user_b,
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount_b,
+    )?;
+    
+    // Close the temporary account, unwrapping the SOL leg to lamports
+    unwrap_sol(
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.temp_wsol.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+    
+    LpPosition::record_withdrawal_if_open(&ctx.accounts.position, lp_amount, Clock::get()?.unix_timestamp)?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquidityNative<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.vault_a,
+        constraint = vault_a.mint == pool.load()?.token_mint_a @ AmmError::InvalidVault,
+        constraint = vault_a.owner == pool.key() @ AmmError::InvalidVault
+    )]
+    pub vault_a: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.vault_b,
+        constraint = vault_b.mint == pool.load()?.token_mint_b @ AmmError::InvalidVault,
+        constraint = vault_b.owner == pool.key() @ AmmError::InvalidVault
+    )]
+    pub vault_b: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.lp_mint,
+        constraint = lp_mint.mint_authority == COption::Some(pool.key()) @ AmmError::InvalidLpMint
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        constraint = user_lp.mint == pool.load()?.lp_mint,
+        constraint = user_lp.owner == user.key()
+    )]
+    pub user_lp: Box<Account<'info, TokenAccount>>,
+    
+    /// CHECK: The user's position PDA, updated in the handler if it exists
+    #[account(
+        mut,
+        seeds = [b"position", pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: UncheckedAccount<'info>,
+    
+    /// User account for the non-native side of the pool
+    #[account(
+        mut,
+        constraint = user_token.owner == user.key()
+    )]
+    pub user_token: Box<Account<'info, TokenAccount>>,
+    
+    /// Temporary wSOL account, closed back to the user before the instruction ends
+    #[account(
+        init,
+        payer = user,
+        seeds = [WSOL_SEED, user.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = user
+    )]
+    pub temp_wsol: Box<Account<'info, TokenAccount>>,
+    
+    #[account(address = native_mint::ID)]
+    pub native_mint: Box<Account<'info, Mint>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, Transfer, Burn};
+use anchor_lang::solana_program::program_option::COption;
+use crate::state::{LpPosition, Pool};
+use crate::error::AmmError;
+use crate::math::get_single_sided_withdraw_amount;
+
+pub fn remove_liquidity_single(
+    ctx: Context<RemoveLiquiditySingle>,
+    lp_amount: u64,
+    token_out: Pubkey,
+    min_amount_out: u64,
+) -> Result<()> {
+    require!(lp_amount > 0, AmmError::ZeroLiquidity);
+    
+    let pool = *ctx.accounts.pool.load()?;
+    
+    // Determine which side of the pool is being withdrawn
+    let (vault_out, reserve_out, reserve_other) = if token_out == pool.token_mint_a {
+        (&ctx.accounts.vault_a, ctx.accounts.vault_a.amount, ctx.accounts.vault_b.amount)
+    } else if token_out == pool.token_mint_b {
+        (&ctx.accounts.vault_b, ctx.accounts.vault_b.amount, ctx.accounts.vault_a.amount)
+    } else {
+        return Err(AmmError::InvalidMint.into());
+    };
+    require!(ctx.accounts.user_token_out.mint == token_out, AmmError::InvalidMint);
+    
+    let lp_supply = ctx.accounts.lp_mint.supply;
+    
+    // The pro-rata share of the output token plus the other share swapped
+    // back through the pool at post-withdrawal reserves
+    let amount_out = get_single_sided_withdraw_amount(
+        lp_amount,
+        reserve_out,
+        reserve_other,
+        lp_supply,
+        pool.fee_bps,
+    )?;
+    require!(amount_out >= min_amount_out, AmmError::SlippageExceeded);
+    
+    // Burn LP tokens from user
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.user_lp.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        lp_amount,
+    )?;
+    
+    // PDA signer seeds
+    let seeds = &[
+        b"pool",
+        pool.token_mint_a.as_ref(),
+        pool.token_mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    // The other share never leaves its vault, so only the output side moves
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: vault_out.to_account_info(),
+                to: ctx.accounts.user_token_out.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount_out,
+    )?;
+    
+    LpPosition::record_withdrawal_if_open(&ctx.accounts.position, lp_amount, Clock::get()?.unix_timestamp)?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquiditySingle<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump,
+        constraint = !pool.load()?.is_emergency_mode() @ AmmError::EmergencyMode
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.vault_a,
+        constraint = vault_a.mint == pool.load()?.token_mint_a @ AmmError::InvalidVault,
+        constraint = vault_a.owner == pool.key() @ AmmError::InvalidVault
+    )]
+    pub vault_a: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.vault_b,
+        constraint = vault_b.mint == pool.load()?.token_mint_b @ AmmError::InvalidVault,
+        constraint = vault_b.owner == pool.key() @ AmmError::InvalidVault
+    )]
+    pub vault_b: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    #[account(
+        mut,
+        address = pool.load()?.lp_mint,
+        constraint = lp_mint.mint_authority == COption::Some(pool.key()) @ AmmError::InvalidLpMint
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        mut,
+        constraint = user_lp.mint == pool.load()?.lp_mint,
+        constraint = user_lp.owner == user.key()
+    )]
+    pub user_lp: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    /// CHECK: The user's position PDA, updated in the handler if it exists
+    #[account(
+        mut,
+        seeds = [b"position", pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub position: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = user_token_out.owner == user.key()
+    )]
+    pub user_token_out: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
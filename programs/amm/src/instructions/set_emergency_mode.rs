@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::{Pool, Config};
+use crate::error::AmmError;
+
+/// Toggle emergency mode on a pool. While enabled, swaps and deposits are
+/// rejected and LPs can only exit through pro-rata `remove_liquidity`.
+pub fn set_emergency_mode(ctx: Context<SetEmergencyMode>, enabled: bool) -> Result<()> {
+    // Only admin can pause pools
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.config.admin,
+        AmmError::Unauthorized
+    );
+    
+    ctx.accounts.pool.load_mut()?.emergency_mode = u8::from(enabled);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetEmergencyMode<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+}
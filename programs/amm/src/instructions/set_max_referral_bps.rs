@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::Config;
+use crate::error::AmmError;
+
+pub fn set_max_referral_bps(ctx: Context<SetMaxReferralBps>, max_referral_bps: u16) -> Result<()> {
+    // Only admin can change protocol limits
+    require!(
+        ctx.accounts.admin.key() == ctx.accounts.config.admin,
+        AmmError::Unauthorized
+    );
+    
+    require!(max_referral_bps <= 10_000, AmmError::ReferralFeeTooHigh);
+    
+    ctx.accounts.config.max_referral_bps = max_referral_bps;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMaxReferralBps<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
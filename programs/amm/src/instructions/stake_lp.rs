@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{Farm, UserStake};
+use crate::error::AmmError;
+
+pub fn stake_lp(ctx: Context<StakeLp>, amount: u64) -> Result<()> {
+    require!(amount > 0, AmmError::ZeroLiquidity);
+    
+    let farm = &mut ctx.accounts.farm;
+    farm.update_rewards(Clock::get()?.unix_timestamp)?;
+    
+    let stake = &mut ctx.accounts.user_stake;
+    if stake.owner == Pubkey::default() {
+        stake.farm = farm.key();
+        stake.owner = ctx.accounts.user.key();
+        stake.bump = ctx.bumps.user_stake;
+    }
+    
+    // Settle rewards on the old balance before it changes
+    stake.settle(farm)?;
+    stake.amount = stake.amount.checked_add(amount).ok_or(AmmError::MathOverflow)?;
+    stake.checkpoint(farm)?;
+    
+    farm.total_staked = farm.total_staked.checked_add(amount).ok_or(AmmError::MathOverflow)?;
+    
+    // Transfer LP tokens from user to farm vault
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_lp.to_account_info(),
+                to: ctx.accounts.lp_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct StakeLp<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"farm", farm.pool.as_ref()],
+        bump = farm.bump
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserStake::LEN,
+        seeds = [b"stake", farm.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+    
+    #[account(
+        mut,
+        address = farm.lp_vault @ AmmError::InvalidVault
+    )]
+    pub lp_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = user_lp.mint == farm.lp_mint @ AmmError::InvalidLpMint,
+        constraint = user_lp.owner == user.key()
+    )]
+    pub user_lp: Box<Account<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
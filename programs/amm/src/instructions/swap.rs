@@ -1,28 +1,40 @@
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token::{self, Mint, Token, Transfer};
-use crate::state::Pool;
+use crate::state::{Pool, Config};
 use crate::error::AmmError;
+use crate::events::SwapEvent;
 use crate::math::get_amount_out;
 
 pub fn swap(
     ctx: Context<Swap>,
     amount_in: u64,
     minimum_amount_out: u64,
+    referral_bps: u16,
+    deadline: Option<i64>,
 ) -> Result<()> {
+    if let Some(deadline) = deadline {
+        require!(Clock::get()?.unix_timestamp <= deadline, AmmError::DeadlineExceeded);
+    }
+    
     require!(amount_in > 0, AmmError::ZeroLiquidity);
     
-    let pool = &ctx.accounts.pool;
+    let pool = *ctx.accounts.pool.load()?;
+    let user_output = match (&ctx.accounts.user_output, &ctx.accounts.user_output_ata) {
+        (Some(account), None) | (None, Some(account)) => account,
+        _ => return Err(AmmError::InvalidOutputAccount.into()),
+    };
     
     // Determine swap direction and get reserves
     let (reserve_in, reserve_out) = if ctx.accounts.vault_input.key() == pool.vault_a {
         require!(ctx.accounts.vault_output.key() == pool.vault_b, AmmError::InvalidVault);
         require!(ctx.accounts.user_input.mint == pool.token_mint_a, AmmError::InvalidVault);
-        require!(ctx.accounts.user_output.mint == pool.token_mint_b, AmmError::InvalidVault);
+        require!(user_output.mint == pool.token_mint_b, AmmError::InvalidVault);
         (ctx.accounts.vault_input.amount, ctx.accounts.vault_output.amount)
     } else if ctx.accounts.vault_input.key() == pool.vault_b {
         require!(ctx.accounts.vault_output.key() == pool.vault_a, AmmError::InvalidVault);
         require!(ctx.accounts.user_input.mint == pool.token_mint_b, AmmError::InvalidVault);
-        require!(ctx.accounts.user_output.mint == pool.token_mint_a, AmmError::InvalidVault);
+        require!(user_output.mint == pool.token_mint_a, AmmError::InvalidVault);
         (ctx.accounts.vault_input.amount, ctx.accounts.vault_output.amount)
     } else {
         return Err(AmmError::InvalidVault.into());
@@ -32,9 +44,30 @@ pub fn swap(
     // This prevents swaps on newly created pools or pools with zero reserves
     require!(reserve_in > 0 && reserve_out > 0, AmmError::PoolNotReady);
     
+    // Referral cut is taken out of the input before pricing, and only when a
+    // referrer token account is supplied
+    require!(
+        referral_bps <= ctx.accounts.config.max_referral_bps,
+        AmmError::ReferralFeeTooHigh
+    );
+    let referral_amount = match ctx.accounts.referrer_token {
+        Some(_) => {
+            let amount = (amount_in as u128)
+                .checked_mul(referral_bps as u128)
+                .ok_or(AmmError::MathOverflow)?
+                .checked_div(10_000u128)
+                .ok_or(AmmError::MathOverflow)?;
+            u64::try_from(amount).map_err(|_| AmmError::MathOverflow)?
+        }
+        None => 0,
+    };
+    let amount_in_after_referral = amount_in
+        .checked_sub(referral_amount)
+        .ok_or(AmmError::MathOverflow)?;
+    
     // Calculate output amount with fee
     let amount_out = get_amount_out(
-        amount_in,
+        amount_in_after_referral,
         reserve_in,
         reserve_out,
         pool.fee_bps,
@@ -43,6 +76,23 @@ pub fn swap(
     require!(amount_out >= minimum_amount_out, AmmError::SlippageExceeded);
     require!(amount_out < reserve_out, AmmError::InsufficientLiquidity);
     
+    // Transfer referral cut from user to referrer
+    if let Some(referrer_token) = &ctx.accounts.referrer_token {
+        if referral_amount > 0 {
+            token::transfer(
+                CpiContext::new(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.user_input.to_account_info(),
+                        to: referrer_token.to_account_info(),
+                        authority: ctx.accounts.user.to_account_info(),
+                    },
+                ),
+                referral_amount,
+            )?;
+        }
+    }
+    
     // Transfer input tokens from user to vault
     token::transfer(
         CpiContext::new(
@@ -53,7 +103,7 @@ pub fn swap(
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
-        amount_in,
+        amount_in_after_referral,
     )?;
     
     // PDA signer seeds
@@ -71,7 +121,7 @@ pub fn swap(
             ctx.accounts.token_program.to_account_info(),
             Transfer {
                 from: ctx.accounts.vault_output.to_account_info(),
-                to: ctx.accounts.user_output.to_account_info(),
+                to: user_output.to_account_info(),
                 authority: ctx.accounts.pool.to_account_info(),
             },
             signer,
@@ -79,6 +129,16 @@ pub fn swap(
         amount_out,
     )?;
     
+    emit!(SwapEvent {
+        pool: ctx.accounts.pool.key(),
+        user: ctx.accounts.user.key(),
+        input_mint: ctx.accounts.user_input.mint,
+        amount_in,
+        amount_out,
+        referrer: ctx.accounts.referrer_token.as_ref().map(|account| account.key()),
+        referral_amount,
+    });
+    
     Ok(())
 }
 
@@ -89,44 +149,77 @@ pub struct Swap<'info> {
     
     #[account(
         mut,
-        seeds = [b"pool", pool.token_mint_a.as_ref(), pool.token_mint_b.as_ref()],
-        bump = pool.bump
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump,
+        constraint = !pool.load()?.is_emergency_mode() @ AmmError::EmergencyMode
     )]
-    pub pool: Box<Account<'info, Pool>>,
+    pub pool: AccountLoader<'info, Pool>,
     
-    #[account(address = pool.token_mint_a)]
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    
+    #[account(address = pool.load()?.token_mint_a)]
     pub token_mint_a: Box<Account<'info, Mint>>,
     
-    #[account(address = pool.token_mint_b)]
+    #[account(address = pool.load()?.token_mint_b)]
     pub token_mint_b: Box<Account<'info, Mint>>,
     
+    /// Mint of the token being bought, used to derive `user_output_ata`
+    #[account(
+        constraint = output_mint.key() == pool.load()?.token_mint_a || output_mint.key() == pool.load()?.token_mint_b @ AmmError::InvalidMint
+    )]
+    pub output_mint: Box<Account<'info, Mint>>,
+    
     #[account(
         mut,
         constraint = user_input.owner == user.key()
     )]
     pub user_input: Box<Account<'info, anchor_spl::token::TokenAccount>>,
     
+    /// Existing account receiving the output; pass this or `user_output_ata`
     #[account(
         mut,
         constraint = user_output.owner == user.key()
     )]
-    pub user_output: Box<Account<'info, anchor_spl::token::TokenAccount>>,
+    pub user_output: Option<Box<Account<'info, anchor_spl::token::TokenAccount>>>,
     
     #[account(
         mut,
         constraint = vault_input.owner == pool.key() @ AmmError::InvalidVault,
-        constraint = vault_input.key() == pool.vault_a || vault_input.key() == pool.vault_b @ AmmError::InvalidVault
+        constraint = vault_input.key() == pool.load()?.vault_a || vault_input.key() == pool.load()?.vault_b @ AmmError::InvalidVault
     )]
     pub vault_input: Box<Account<'info, anchor_spl::token::TokenAccount>>,
     
     #[account(
         mut,
         constraint = vault_output.owner == pool.key() @ AmmError::InvalidVault,
-        constraint = vault_output.key() == pool.vault_a || vault_output.key() == pool.vault_b @ AmmError::InvalidVault,
+        constraint = vault_output.key() == pool.load()?.vault_a || vault_output.key() == pool.load()?.vault_b @ AmmError::InvalidVault,
         constraint = vault_output.key() != vault_input.key() @ AmmError::InvalidVault
     )]
     pub vault_output: Box<Account<'info, anchor_spl::token::TokenAccount>>,
     
+    /// Optional integrator account receiving the referral cut, in the input token
+    #[account(
+        mut,
+        constraint = referrer_token.mint == user_input.mint @ AmmError::InvalidMint
+    )]
+    pub referrer_token: Option<Box<Account<'info, anchor_spl::token::TokenAccount>>>,
+    
+    /// The user's associated token account for the output mint, created on the
+    /// first purchase of this token so new users need no setup instruction
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = output_mint,
+        associated_token::authority = user
+    )]
+    pub user_output_ata: Option<Box<Account<'info, anchor_spl::token::TokenAccount>>>,
+    
     pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
 }
 
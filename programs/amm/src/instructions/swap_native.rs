@@ -0,0 +1,188 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token::spl_token::native_mint;
+use crate::state::Pool;
+use crate::error::AmmError;
+use crate::events::SwapEvent;
+use crate::math::get_amount_out;
+use crate::native_sol::{wrap_sol, unwrap_sol, WSOL_SEED};
+
+/// Swap against a pool with the native mint on one side, paying or receiving
+/// plain lamports. The wSOL leg goes through a temporary account that is created
+/// and closed within the instruction.
+pub fn swap_native(
+    ctx: Context<SwapNative>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    deadline: Option<i64>,
+) -> Result<()> {
+    if let Some(deadline) = deadline {
+        require!(Clock::get()?.unix_timestamp <= deadline, AmmError::DeadlineExceeded);
+    }
+    
+    require!(amount_in > 0, AmmError::ZeroLiquidity);
+    
+    let pool = *ctx.accounts.pool.load()?;
+    
+    // Determine swap direction and get reserves
+    let (reserve_in, reserve_out) = if ctx.accounts.vault_input.key() == pool.vault_a {
+        require!(ctx.accounts.vault_output.key() == pool.vault_b, AmmError::InvalidVault);
+        (ctx.accounts.vault_input.amount, ctx.accounts.vault_output.amount)
+    } else if ctx.accounts.vault_input.key() == pool.vault_b {
+        require!(ctx.accounts.vault_output.key() == pool.vault_a, AmmError::InvalidVault);
+        (ctx.accounts.vault_input.amount, ctx.accounts.vault_output.amount)
+    } else {
+        return Err(AmmError::InvalidVault.into());
+    };
+    
+    // Exactly one side is native SOL; the user supplies a token account for the other
+    let sol_in = ctx.accounts.vault_input.mint == native_mint::ID;
+    let token_mint = if sol_in {
+        ctx.accounts.vault_output.mint
+    } else {
+        require!(ctx.accounts.vault_output.mint == native_mint::ID, AmmError::InvalidMint);
+        ctx.accounts.vault_input.mint
+    };
+    require!(ctx.accounts.user_token.mint == token_mint, AmmError::InvalidMint);
+    
+    // Pool readiness: ensure both reserves have liquidity before allowing swaps
+    require!(reserve_in > 0 && reserve_out > 0, AmmError::PoolNotReady);
+    
+    // Calculate output amount with fee
+    let amount_out = get_amount_out(
+        amount_in,
+        reserve_in,
+        reserve_out,
+        pool.fee_bps,
+    )?;
+    
+    require!(amount_out >= minimum_amount_out, AmmError::SlippageExceeded);
+    require!(amount_out < reserve_out, AmmError::InsufficientLiquidity);
+    
+    // Transfer input tokens from user to vault, wrapping lamports first if needed
+    let user_input = if sol_in {
+        wrap_sol(
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.temp_wsol.to_account_info(),
+            amount_in,
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        )?;
+        ctx.accounts.temp_wsol.to_account_info()
+    } else {
+        ctx.accounts.user_token.to_account_info()
+    };
+    
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: user_input,
+                to: ctx.accounts.vault_input.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount_in,
+    )?;
+    
+    // PDA signer seeds
+    let seeds = &[
+        b"pool",
+        pool.token_mint_a.as_ref(),
+        pool.token_mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    // Transfer output tokens from vault to user, via the temporary account for SOL
+    let user_output = if sol_in {
+        ctx.accounts.user_token.to_account_info()
+    } else {
+        ctx.accounts.temp_wsol.to_account_info()
+    };
+    
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_output.to_account_info(),
+                to: user_output,
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount_out,
+    )?;
+    
+    // Close the temporary account, unwrapping any SOL output and refunding rent
+    unwrap_sol(
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.temp_wsol.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+    )?;
+    
+    emit!(SwapEvent {
+        pool: ctx.accounts.pool.key(),
+        user: ctx.accounts.user.key(),
+        input_mint: ctx.accounts.vault_input.mint,
+        amount_in,
+        amount_out,
+        referrer: None,
+        referral_amount: 0,
+    });
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SwapNative<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"pool", pool.load()?.token_mint_a.as_ref(), pool.load()?.token_mint_b.as_ref()],
+        bump = pool.load()?.bump,
+        constraint = !pool.load()?.is_emergency_mode() @ AmmError::EmergencyMode
+    )]
+    pub pool: AccountLoader<'info, Pool>,
+    
+    #[account(
+        mut,
+        constraint = vault_input.owner == pool.key() @ AmmError::InvalidVault,
+        constraint = vault_input.key() == pool.load()?.vault_a || vault_input.key() == pool.load()?.vault_b @ AmmError::InvalidVault
+    )]
+    pub vault_input: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = vault_output.owner == pool.key() @ AmmError::InvalidVault,
+        constraint = vault_output.key() == pool.load()?.vault_a || vault_output.key() == pool.load()?.vault_b @ AmmError::InvalidVault,
+        constraint = vault_output.key() != vault_input.key() @ AmmError::InvalidVault
+    )]
+    pub vault_output: Box<Account<'info, TokenAccount>>,
+    
+    /// User account for the non-native side of the pool
+    #[account(
+        mut,
+        constraint = user_token.owner == user.key()
+    )]
+    pub user_token: Box<Account<'info, TokenAccount>>,
+    
+    /// Temporary wSOL account, closed back to the user before the instruction ends
+    #[account(
+        init,
+        payer = user,
+        seeds = [WSOL_SEED, user.key().as_ref()],
+        bump,
+        token::mint = native_mint,
+        token::authority = user
+    )]
+    pub temp_wsol: Box<Account<'info, TokenAccount>>,
+    
+    #[account(address = native_mint::ID)]
+    pub native_mint: Box<Account<'info, Mint>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
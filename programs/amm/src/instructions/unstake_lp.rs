@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::{Farm, UserStake};
+use crate::error::AmmError;
+
+pub fn unstake_lp(ctx: Context<UnstakeLp>, amount: u64) -> Result<()> {
+    require!(amount > 0, AmmError::ZeroLiquidity);
+    
+    let farm = &mut ctx.accounts.farm;
+    farm.update_rewards(Clock::get()?.unix_timestamp)?;
+    
+    let stake = &mut ctx.accounts.user_stake;
+    require!(amount <= stake.amount, AmmError::InsufficientStake);
+    
+    // Settle rewards on the old balance before it changes
+    stake.settle(farm)?;
+    stake.amount -= amount;
+    stake.checkpoint(farm)?;
+    
+    farm.total_staked = farm.total_staked.checked_sub(amount).ok_or(AmmError::MathOverflow)?;
+    
+    // Farm PDA signer seeds
+    let farm_pool = farm.pool;
+    let seeds = &[
+        b"farm",
+        farm_pool.as_ref(),
+        &[farm.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    // Transfer LP tokens from farm vault to user
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.lp_vault.to_account_info(),
+                to: ctx.accounts.user_lp.to_account_info(),
+                authority: ctx.accounts.farm.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+    )?;
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UnstakeLp<'info> {
+    pub user: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"farm", farm.pool.as_ref()],
+        bump = farm.bump
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+    
+    #[account(
+        mut,
+        seeds = [b"stake", farm.key().as_ref(), user.key().as_ref()],
+        bump = user_stake.bump
+    )]
+    pub user_stake: Box<Account<'info, UserStake>>,
+    
+    #[account(
+        mut,
+        address = farm.lp_vault @ AmmError::InvalidVault
+    )]
+    pub lp_vault: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = user_lp.mint == farm.lp_mint @ AmmError::InvalidLpMint,
+        constraint = user_lp.owner == user.key()
+    )]
+    pub user_lp: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
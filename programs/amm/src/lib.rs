@@ -4,7 +4,10 @@ declare_id!("GusCwDKH6aEkejKcGKDoVpRaeYPBRHwxn1k5kGFK4Guu");
 
 pub mod state;
 pub mod error;
+pub mod events;
 pub mod math;
+pub mod native_sol;
+pub mod migration;
 pub mod instructions;
 
 use instructions::*;
@@ -21,28 +24,184 @@ pub mod amm {
         instructions::initialize_pool::initialize_pool(ctx)
     }
 
+    pub fn close_pool(ctx: Context<ClosePool>) -> Result<()> {
+        instructions::close_pool::close_pool(ctx)
+    }
+
     pub fn add_liquidity(
         ctx: Context<AddLiquidity>,
-        amount_a: u64,
-        amount_b: u64,
+        amount_a_max: u64,
+        amount_b_max: u64,
+        amount_a_min: u64,
+        amount_b_min: u64,
+        min_lp_tokens: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        instructions::add_liquidity::add_liquidity(
+            ctx,
+            amount_a_max,
+            amount_b_max,
+            amount_a_min,
+            amount_b_min,
+            min_lp_tokens,
+            deadline,
+        )
+    }
+
+    pub fn add_liquidity_single(
+        ctx: Context<AddLiquiditySingle>,
+        token_in: Pubkey,
+        amount_in: u64,
+        min_lp_tokens: u64,
+    ) -> Result<()> {
+        instructions::add_liquidity_single::add_liquidity_single(ctx, token_in, amount_in, min_lp_tokens)
+    }
+
+    pub fn add_liquidity_exact_lp(
+        ctx: Context<AddLiquidityExactLp>,
+        lp_out: u64,
+        max_amount_a: u64,
+        max_amount_b: u64,
+    ) -> Result<()> {
+        instructions::add_liquidity_exact_lp::add_liquidity_exact_lp(ctx, lp_out, max_amount_a, max_amount_b)
+    }
+
+    pub fn add_liquidity_native(
+        ctx: Context<AddLiquidityNative>,
+        amount_a_max: u64,
+        amount_b_max: u64,
+        amount_a_min: u64,
+        amount_b_min: u64,
         min_lp_tokens: u64,
+        deadline: Option<i64>,
     ) -> Result<()> {
-        instructions::add_liquidity::add_liquidity(ctx, amount_a, amount_b, min_lp_tokens)
+        instructions::add_liquidity_native::add_liquidity_native(
+            ctx,
+            amount_a_max,
+            amount_b_max,
+            amount_a_min,
+            amount_b_min,
+            min_lp_tokens,
+            deadline,
+        )
     }
 
     pub fn remove_liquidity(
         ctx: Context<RemoveLiquidity>,
         lp_amount: u64,
+        min_amount_a: u64,
+        min_amount_b: u64,
+        deadline: Option<i64>,
     ) -> Result<()> {
-        instructions::remove_liquidity::remove_liquidity(ctx, lp_amount)
+        instructions::remove_liquidity::remove_liquidity(ctx, lp_amount, min_amount_a, min_amount_b, deadline)
+    }
+
+    pub fn remove_liquidity_single(
+        ctx: Context<RemoveLiquiditySingle>,
+        lp_amount: u64,
+        token_out: Pubkey,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        instructions::remove_liquidity_single::remove_liquidity_single(ctx, lp_amount, token_out, min_amount_out)
+    }
+
+    pub fn remove_liquidity_exact_out(
+        ctx: Context<RemoveLiquidityExactOut>,
+        amount_a: u64,
+        amount_b: u64,
+        max_lp_in: u64,
+    ) -> Result<()> {
+        instructions::remove_liquidity_exact_out::remove_liquidity_exact_out(ctx, amount_a, amount_b, max_lp_in)
+    }
+
+    pub fn remove_liquidity_native(
+        ctx: Context<RemoveLiquidityNative>,
+        lp_amount: u64,
+        min_amount_a: u64,
+        min_amount_b: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        instructions::remove_liquidity_native::remove_liquidity_native(ctx, lp_amount, min_amount_a, min_amount_b, deadline)
+    }
+
+    pub fn get_position_stats(ctx: Context<GetPositionStats>) -> Result<PositionStats> {
+        instructions::get_position_stats::get_position_stats(ctx)
     }
 
     pub fn swap(
         ctx: Context<Swap>,
         amount_in: u64,
         minimum_amount_out: u64,
+        referral_bps: u16,
+        deadline: Option<i64>,
     ) -> Result<()> {
-        instructions::swap::swap(ctx, amount_in, minimum_amount_out)
+        instructions::swap::swap(ctx, amount_in, minimum_amount_out, referral_bps, deadline)
+    }
+
+    pub fn swap_native(
+        ctx: Context<SwapNative>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        instructions::swap_native::swap_native(ctx, amount_in, minimum_amount_out, deadline)
+    }
+
+    pub fn set_max_referral_bps(ctx: Context<SetMaxReferralBps>, max_referral_bps: u16) -> Result<()> {
+        instructions::set_max_referral_bps::set_max_referral_bps(ctx, max_referral_bps)
+    }
+
+    pub fn set_emergency_mode(ctx: Context<SetEmergencyMode>, enabled: bool) -> Result<()> {
+        instructions::set_emergency_mode::set_emergency_mode(ctx, enabled)
+    }
+
+    pub fn recover_tokens(ctx: Context<RecoverTokens>, amount: u64) -> Result<()> {
+        instructions::recover_tokens::recover_tokens(ctx, amount)
+    }
+
+    pub fn initialize_farm(ctx: Context<InitializeFarm>) -> Result<()> {
+        instructions::initialize_farm::initialize_farm(ctx)
+    }
+
+    pub fn add_reward_stream(ctx: Context<AddRewardStream>, funder: Pubkey) -> Result<()> {
+        instructions::add_reward_stream::add_reward_stream(ctx, funder)
+    }
+
+    pub fn fund_reward_stream(
+        ctx: Context<FundRewardStream>,
+        stream_index: u8,
+        amount: u64,
+        duration: i64,
+    ) -> Result<()> {
+        instructions::fund_reward_stream::fund_reward_stream(ctx, stream_index, amount, duration)
+    }
+
+    pub fn stake_lp(ctx: Context<StakeLp>, amount: u64) -> Result<()> {
+        instructions::stake_lp::stake_lp(ctx, amount)
+    }
+
+    pub fn unstake_lp(ctx: Context<UnstakeLp>, amount: u64) -> Result<()> {
+        instructions::unstake_lp::unstake_lp(ctx, amount)
+    }
+
+    pub fn emergency_unstake_lp(ctx: Context<UnstakeLp>) -> Result<()> {
+        instructions::emergency_unstake_lp::emergency_unstake_lp(ctx)
+    }
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, stream_index: u8) -> Result<()> {
+        instructions::claim_rewards::claim_rewards(ctx, stream_index)
+    }
+
+    pub fn claim_all_rewards<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimAllRewards<'info>>) -> Result<()> {
+        instructions::claim_all_rewards::claim_all_rewards(ctx)
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate_config::migrate_config(ctx)
+    }
+
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        instructions::migrate_pool::migrate_pool(ctx)
     }
 }
 
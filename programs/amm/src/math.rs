@@ -1,21 +1,18 @@
+//! On-chain entry points to `amm_math`, with errors mapped to `AmmError`.
+//! The math itself lives in the `no_std` crate so off-chain code can share it.
+
 use anchor_lang::prelude::*;
 use crate::error::AmmError;
 
+pub use amm_math::{integer_sqrt, FEE_GROWTH_PRECISION, REWARD_PRECISION, U256};
+
 /// Calculate proportional amount based on reserves
 pub fn quote(
     amount_a: u64,
     reserve_a: u64,
     reserve_b: u64,
 ) -> Result<u64> {
-    require!(reserve_a > 0, AmmError::InsufficientLiquidity);
-    
-    let amount_b = (amount_a as u128)
-        .checked_mul(reserve_b as u128)
-        .ok_or(AmmError::MathOverflow)?
-        .checked_div(reserve_a as u128)
-        .ok_or(AmmError::MathOverflow)?;
-    
-    u64::try_from(amount_b).map_err(|_| AmmError::MathOverflow.into())
+    Ok(amm_math::quote(amount_a, reserve_a, reserve_b).map_err(AmmError::from)?)
 }
 
 /// Calculate output amount with fee deduction using constant product formula
@@ -25,48 +22,149 @@ pub fn get_amount_out(
     reserve_out: u64,
     fee_bps: u16,
 ) -> Result<u64> {
-    require!(reserve_in > 0 && reserve_out > 0, AmmError::InsufficientLiquidity);
-    require!(amount_in > 0, AmmError::ZeroLiquidity);
-    
-    let fee_complement = 10_000u128
-        .checked_sub(fee_bps as u128)
-        .ok_or(AmmError::MathOverflow)?;
-    
-    let amount_in_with_fee = (amount_in as u128)
-        .checked_mul(fee_complement)
-        .ok_or(AmmError::MathOverflow)?;
-    
-    let numerator = (reserve_out as u128)
-        .checked_mul(amount_in_with_fee)
-        .ok_or(AmmError::MathOverflow)?;
-    
-    let denominator = (reserve_in as u128)
-        .checked_mul(10_000u128)
-        .ok_or(AmmError::MathOverflow)?
-        .checked_add(amount_in_with_fee)
-        .ok_or(AmmError::MathOverflow)?;
-    
-    let amount_out = numerator
-        .checked_div(denominator)
-        .ok_or(AmmError::MathOverflow)?;
-    
-    u64::try_from(amount_out).map_err(|_| AmmError::MathOverflow.into())
-}
-
-/// Calculate integer square root using Newton's method
-pub fn integer_sqrt(value: u128) -> u128 {
-    if value == 0 {
-        return 0;
-    }
-    
-    let mut x = value;
-    let mut y = (x + 1) / 2;
-    
-    while y < x {
-        x = y;
-        y = (x + value / x) / 2;
-    }
-    
-    x
+    Ok(amm_math::get_amount_out(amount_in, reserve_in, reserve_out, fee_bps).map_err(AmmError::from)?)
+}
+
+/// Calculate how much of a single-token deposit to swap so that the remainder
+/// and the swap output match the post-swap reserve ratio.
+pub fn get_single_sided_swap_amount(
+    amount_in: u64,
+    reserve_in: u64,
+    fee_bps: u16,
+) -> Result<u64> {
+    Ok(amm_math::get_single_sided_swap_amount(amount_in, reserve_in, fee_bps).map_err(AmmError::from)?)
+}
+
+/// Calculate `amount * numerator / denominator`, rounding up
+pub fn mul_div_ceil(
+    amount: u64,
+    numerator: u64,
+    denominator: u64,
+) -> Result<u64> {
+    Ok(amm_math::mul_div_ceil(amount, numerator, denominator).map_err(AmmError::from)?)
+}
+
+/// Calculate the LP tokens to burn for withdrawing exact token amounts, rounding up.
+pub fn get_lp_for_exact_withdrawal(
+    amount_a: u64,
+    amount_b: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+    fee_bps: u16,
+) -> Result<u64> {
+    Ok(amm_math::get_lp_for_exact_withdrawal(amount_a, amount_b, reserve_a, reserve_b, lp_supply, fee_bps).map_err(AmmError::from)?)
+}
+
+/// Calculate the token amounts to pay for minting exactly `lp_out`, rounded up
+pub fn get_exact_lp_deposit_amounts(
+    lp_out: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+) -> Result<(u64, u64)> {
+    Ok(amm_math::get_exact_lp_deposit_amounts(lp_out, reserve_a, reserve_b, lp_supply).map_err(AmmError::from)?)
+}
+
+/// Calculate the LP tokens minted for a single-token deposit of `amount_in`
+pub fn get_single_sided_deposit_lp(
+    amount_in: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    lp_supply: u64,
+    fee_bps: u16,
+) -> Result<u64> {
+    Ok(amm_math::get_single_sided_deposit_lp(amount_in, reserve_in, reserve_out, lp_supply, fee_bps).map_err(AmmError::from)?)
 }
 
+/// Calculate the amount of one token received for burning `lp_amount`
+pub fn get_single_sided_withdraw_amount(
+    lp_amount: u64,
+    reserve_out: u64,
+    reserve_other: u64,
+    lp_supply: u64,
+    fee_bps: u16,
+) -> Result<u64> {
+    Ok(amm_math::get_single_sided_withdraw_amount(lp_amount, reserve_out, reserve_other, lp_supply, fee_bps).map_err(AmmError::from)?)
+}
+
+/// Calculate deposit amounts and LP tokens to mint for an add_liquidity request.
+pub fn get_deposit_amounts(
+    amount_a_max: u64,
+    amount_b_max: u64,
+    amount_a_min: u64,
+    amount_b_min: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+) -> Result<(u64, u64, u64)> {
+    Ok(amm_math::get_deposit_amounts(amount_a_max, amount_b_max, amount_a_min, amount_b_min, reserve_a, reserve_b, lp_supply).map_err(AmmError::from)?)
+}
+
+/// Calculate proportional withdrawal amounts for burning LP tokens
+pub fn get_withdraw_amounts(
+    lp_amount: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+) -> Result<(u64, u64)> {
+    Ok(amm_math::get_withdraw_amounts(lp_amount, reserve_a, reserve_b, lp_supply).map_err(AmmError::from)?)
+}
+
+/// Advance a farm's reward-per-share accumulator by `elapsed` seconds of emissions
+pub fn get_acc_reward_per_share(
+    acc_reward_per_share: u128,
+    reward_per_second: u64,
+    elapsed: u64,
+    total_staked: u64,
+) -> Result<u128> {
+    Ok(amm_math::get_acc_reward_per_share(acc_reward_per_share, reward_per_second, elapsed, total_staked).map_err(AmmError::from)?)
+}
+
+/// Calculate the reward checkpoint for a staked amount, in reward token units
+pub fn get_reward_debt(
+    staked_amount: u64,
+    acc_reward_per_share: u128,
+) -> Result<u128> {
+    Ok(amm_math::get_reward_debt(staked_amount, acc_reward_per_share).map_err(AmmError::from)?)
+}
+
+/// Calculate rewards earned by a staked amount since its last checkpoint
+pub fn get_pending_reward(
+    staked_amount: u64,
+    acc_reward_per_share: u128,
+    reward_debt: u128,
+) -> Result<u64> {
+    Ok(amm_math::get_pending_reward(staked_amount, acc_reward_per_share, reward_debt).map_err(AmmError::from)?)
+}
+
+/// Pool value per LP token as `sqrt(reserve_a * reserve_b) / lp_supply`, scaled
+/// by `FEE_GROWTH_PRECISION`. Swap fees stay in the reserves, so this only grows
+/// as fees are collected and is unaffected by price moves.
+pub fn get_sqrt_k_per_lp(
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+) -> Result<u128> {
+    Ok(amm_math::get_sqrt_k_per_lp(reserve_a, reserve_b, lp_supply).map_err(AmmError::from)?)
+}
+
+/// Portion of a redeemable amount earned from fees since the position entered
+/// at `entry_sqrt_k_per_lp`
+pub fn get_fees_earned(
+    amount: u64,
+    entry_sqrt_k_per_lp: u128,
+    sqrt_k_per_lp: u128,
+) -> Result<u64> {
+    Ok(amm_math::get_fees_earned(amount, entry_sqrt_k_per_lp, sqrt_k_per_lp).map_err(AmmError::from)?)
+}
+
+/// Impermanent loss in basis points of the value the deposited tokens would
+/// have if simply held. Both values must be in the same unit and exclude fees.
+/// Negative when the position is worth more than holding.
+pub fn get_impermanent_loss_bps(
+    hold_value: u64,
+    position_value: u64,
+) -> Result<i64> {
+    Ok(amm_math::get_impermanent_loss_bps(hold_value, position_value).map_err(AmmError::from)?)
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Grow a program-owned account to `len` bytes, zero-filling the new space and
+/// topping up its rent from `payer`. Accounts already that large are left as is.
+pub fn grow_account<'info>(
+    account: AccountInfo<'info>,
+    payer: AccountInfo<'info>,
+    len: usize,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    if account.data_len() >= len {
+        return Ok(());
+    }
+    
+    let rent = Rent::get()?
+        .minimum_balance(len)
+        .saturating_sub(account.lamports());
+    if rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program,
+                system_program::Transfer {
+                    from: payer,
+                    to: account.clone(),
+                },
+            ),
+            rent,
+        )?;
+    }
+    
+    account.realloc(len, true)?;
+    
+    Ok(())
+}
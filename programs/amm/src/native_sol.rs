@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, CloseAccount, SyncNative};
+
+/// Seed for the per-user temporary wSOL account used by the `*_native` instructions
+pub const WSOL_SEED: &[u8] = b"wsol";
+
+/// Move lamports from the user into a wSOL token account and sync its token balance
+pub fn wrap_sol<'info>(
+    user: AccountInfo<'info>,
+    wsol_account: AccountInfo<'info>,
+    amount: u64,
+    system_program: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    system_program::transfer(
+        CpiContext::new(
+            system_program,
+            system_program::Transfer {
+                from: user,
+                to: wsol_account.clone(),
+            },
+        ),
+        amount,
+    )?;
+    
+    token::sync_native(CpiContext::new(
+        token_program,
+        SyncNative {
+            account: wsol_account,
+        },
+    ))
+}
+
+/// Close a wSOL token account owned by the user, returning its balance and rent as lamports
+pub fn unwrap_sol<'info>(
+    user: AccountInfo<'info>,
+    wsol_account: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    token::close_account(CpiContext::new(
+        token_program,
+        CloseAccount {
+            account: wsol_account,
+            destination: user.clone(),
+            authority: user,
+        },
+    ))
+}
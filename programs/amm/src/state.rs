@@ -1,7 +1,15 @@
 use anchor_lang::prelude::*;
+use crate::error::AmmError;
+use crate::math::{get_acc_reward_per_share, get_pending_reward, get_reward_debt};
 
-/// Pool account holding AMM state and vault references
-#[account]
+/// Pool account holding AMM state and vault references.
+///
+/// Zero-copy so instructions read it in place instead of Borsh-decoding it.
+/// Handlers copy it out of `load()` rather than holding the borrow, since the
+/// pool signs the token CPIs that follow.
+/// The `#[repr(C)]` layout is part of the on-chain format: new fields are
+/// carved out of `reserved` and existing fields are never reordered.
+#[account(zero_copy)]
 pub struct Pool {
     /// First token mint in the pair
     pub token_mint_a: Pubkey,
@@ -17,13 +25,35 @@ pub struct Pool {
     pub fee_bps: u16,
     /// PDA bump seed
     pub bump: u8,
+    /// Non-zero when only pro-rata withdrawals are allowed
+    pub emergency_mode: u8,
+    /// Layout version, `POOL_VERSION` once created or migrated
+    pub version: u8,
+    /// Aligns `reserved` to 8 bytes so it can hold `u64` fields
+    pub padding: [u8; 3],
+    /// Space for future fields such as oracle and fee accumulators
+    pub reserved: [u8; 128],
 }
 
 impl Pool {
     /// Space required for the Pool account
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 2 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 32 + 32 + 2 + 1 + 1 + 1 + 3 + 128;
+
+    /// Whether swaps and deposits are currently rejected
+    pub fn is_emergency_mode(&self) -> bool {
+        self.emergency_mode != 0
+    }
 }
 
+const _: () = assert!(Pool::LEN == 8 + std::mem::size_of::<Pool>());
+
+/// Current `Pool` layout. Version 0 pools predate the field, including the
+/// Borsh-encoded ones whose bytes are a prefix of the zero-copy layout.
+pub const POOL_VERSION: u8 = 1;
+
+/// Current `Config` layout. Version 0 configs predate the field.
+pub const CONFIG_VERSION: u8 = 1;
+
 /// Global config account holding admin authority
 #[account]
 pub struct Config {
@@ -31,10 +61,260 @@ pub struct Config {
     pub admin: Pubkey,
     /// PDA bump seed
     pub bump: u8,
+    /// Upper bound on the referral fee integrators may take on swaps, in basis points
+    pub max_referral_bps: u16,
+    /// Layout version, `CONFIG_VERSION` once created or migrated
+    pub version: u8,
+    /// Space for future fields, zero until used
+    pub reserved: [u8; 64],
 }
 
 impl Config {
     /// Space required for the Config account
-    pub const LEN: usize = 8 + 32 + 1;
+    pub const LEN: usize = 8 + 32 + 1 + 2 + 1 + 64;
+}
+
+/// A user's liquidity in a pool, tracked for fee and impermanent loss reporting.
+/// LP minted and burned by every liquidity instruction is counted; LP received
+/// by transfer is not.
+#[account]
+pub struct LpPosition {
+    /// Pool the position belongs to
+    pub pool: Pubkey,
+    /// Liquidity provider
+    pub owner: Pubkey,
+    /// LP tokens attributed to this position
+    pub lp_amount: u64,
+    /// Token A deposited for the LP still held, reduced pro-rata on withdrawal
+    pub entry_amount_a: u64,
+    /// Token B deposited for the LP still held, reduced pro-rata on withdrawal
+    pub entry_amount_b: u64,
+    /// LP-weighted average of `sqrt(k) / lp_supply` at entry, scaled by `FEE_GROWTH_PRECISION`
+    pub entry_sqrt_k_per_lp: u128,
+    /// Time of the first deposit into the position
+    pub opened_at: i64,
+    /// Time of the last deposit or withdrawal
+    pub last_update_ts: i64,
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl LpPosition {
+    /// Space required for the LpPosition account
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 16 + 8 + 8 + 1;
+    
+    /// Fill in the owner of a position just created by `init_if_needed`
+    pub fn open(&mut self, pool: Pubkey, owner: Pubkey, bump: u8) {
+        if self.owner == Pubkey::default() {
+            self.pool = pool;
+            self.owner = owner;
+            self.bump = bump;
+        }
+    }
+    
+    /// Record LP minted for a deposit, with `sqrt_k_per_lp` measured after the deposit
+    pub fn record_deposit(
+        &mut self,
+        lp_amount: u64,
+        amount_a: u64,
+        amount_b: u64,
+        sqrt_k_per_lp: u128,
+        now: i64,
+    ) -> Result<()> {
+        require!(lp_amount > 0, AmmError::ZeroLiquidity);
+        let total = self.lp_amount.checked_add(lp_amount).ok_or(AmmError::MathOverflow)?;
+        
+        // Weighting by LP keeps the fee growth of earlier deposits intact.
+        // Rounding up never over-reports fees.
+        let weighted = (self.lp_amount as u128)
+            .checked_mul(self.entry_sqrt_k_per_lp)
+            .ok_or(AmmError::MathOverflow)?
+            .checked_add(
+                (lp_amount as u128)
+                    .checked_mul(sqrt_k_per_lp)
+                    .ok_or(AmmError::MathOverflow)?,
+            )
+            .ok_or(AmmError::MathOverflow)?;
+        
+        self.entry_sqrt_k_per_lp = weighted.div_ceil(total as u128);
+        self.entry_amount_a = self.entry_amount_a.checked_add(amount_a).ok_or(AmmError::MathOverflow)?;
+        self.entry_amount_b = self.entry_amount_b.checked_add(amount_b).ok_or(AmmError::MathOverflow)?;
+        self.lp_amount = total;
+        if self.opened_at == 0 {
+            self.opened_at = now;
+        }
+        self.last_update_ts = now;
+        
+        Ok(())
+    }
+    
+    /// Record LP burned for a withdrawal. LP beyond what the position tracks
+    /// (e.g. received by transfer) is ignored.
+    pub fn record_withdrawal(&mut self, lp_amount: u64, now: i64) -> Result<()> {
+        let removed = lp_amount.min(self.lp_amount);
+        if removed == 0 {
+            return Ok(());
+        }
+        
+        let remaining = self.lp_amount - removed;
+        self.entry_amount_a = ((self.entry_amount_a as u128) * (remaining as u128) / (self.lp_amount as u128)) as u64;
+        self.entry_amount_b = ((self.entry_amount_b as u128) * (remaining as u128) / (self.lp_amount as u128)) as u64;
+        self.lp_amount = remaining;
+        if remaining == 0 {
+            // A later deposit opens the position afresh
+            self.entry_amount_a = 0;
+            self.entry_amount_b = 0;
+            self.entry_sqrt_k_per_lp = 0;
+            self.opened_at = 0;
+        }
+        self.last_update_ts = now;
+        
+        Ok(())
+    }
+    
+    /// Record a withdrawal against the position stored in `account`, if it
+    /// exists. Withdrawals never create a position: holders of transferred LP
+    /// have none, and an emergency exit should not cost rent.
+    pub fn record_withdrawal_if_open(account: &AccountInfo, lp_amount: u64, now: i64) -> Result<()> {
+        if account.owner != &crate::ID {
+            return Ok(());
+        }
+        
+        let mut position = LpPosition::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        position.record_withdrawal(lp_amount, now)?;
+        let mut data = account.try_borrow_mut_data()?;
+        position.try_serialize(&mut &mut data[..])
+    }
+}
+
+
+/// Maximum number of concurrent reward streams per farm
+pub const MAX_REWARD_STREAMS: usize = 3;
+
+/// A single reward token emitted to a farm's stakers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RewardStream {
+    /// Token paid out as rewards, default when the slot is unused
+    pub reward_mint: Pubkey,
+    /// Vault holding undistributed rewards
+    pub reward_vault: Pubkey,
+    /// Authority allowed to fund and extend this stream
+    pub funder: Pubkey,
+    /// Rewards emitted per second across all stakers
+    pub reward_per_second: u64,
+    /// Rewards accumulated per staked LP token, scaled by `REWARD_PRECISION`
+    pub acc_reward_per_share: u128,
+    /// Last time `acc_reward_per_share` was brought up to date
+    pub last_update_ts: i64,
+    /// Time at which the current reward period ends
+    pub end_ts: i64,
 }
 
+impl RewardStream {
+    /// Space required for a RewardStream
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 16 + 8 + 8;
+    
+    /// Whether this slot holds a configured stream
+    pub fn is_active(&self) -> bool {
+        self.reward_mint != Pubkey::default()
+    }
+    
+    /// Accrue emissions up to `now`, capped at the end of the reward period
+    pub fn update_rewards(&mut self, now: i64, total_staked: u64) -> Result<()> {
+        let until = now.min(self.end_ts);
+        if until <= self.last_update_ts {
+            return Ok(());
+        }
+        
+        let elapsed = u64::try_from(until - self.last_update_ts).map_err(|_| AmmError::MathOverflow)?;
+        self.acc_reward_per_share = get_acc_reward_per_share(
+            self.acc_reward_per_share,
+            self.reward_per_second,
+            elapsed,
+            total_staked,
+        )?;
+        self.last_update_ts = until;
+        
+        Ok(())
+    }
+}
+
+/// Farm distributing up to `MAX_REWARD_STREAMS` reward tokens to LP tokens staked for a pool
+#[account]
+pub struct Farm {
+    /// Pool whose LP tokens are staked
+    pub pool: Pubkey,
+    /// LP token mint of the pool
+    pub lp_mint: Pubkey,
+    /// Vault holding staked LP tokens
+    pub lp_vault: Pubkey,
+    /// Total LP tokens currently staked
+    pub total_staked: u64,
+    /// PDA bump seed
+    pub bump: u8,
+    /// Concurrent reward streams, each with its own funder and schedule
+    pub reward_streams: [RewardStream; MAX_REWARD_STREAMS],
+}
+
+impl Farm {
+    /// Space required for the Farm account
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 1 + RewardStream::LEN * MAX_REWARD_STREAMS;
+    
+    /// Accrue emissions of every active stream up to `now`
+    pub fn update_rewards(&mut self, now: i64) -> Result<()> {
+        let total_staked = self.total_staked;
+        for stream in self.reward_streams.iter_mut().filter(|stream| stream.is_active()) {
+            stream.update_rewards(now, total_staked)?;
+        }
+        Ok(())
+    }
+    
+    /// Look up an active stream by index
+    pub fn stream(&self, index: u8) -> Result<&RewardStream> {
+        self.reward_streams
+            .get(index as usize)
+            .filter(|stream| stream.is_active())
+            .ok_or(AmmError::InvalidRewardStream.into())
+    }
+}
+
+/// A user's staked LP position in a farm
+#[account]
+pub struct UserStake {
+    /// Farm the position belongs to
+    pub farm: Pubkey,
+    /// Owner of the staked LP tokens
+    pub owner: Pubkey,
+    /// LP tokens staked
+    pub amount: u64,
+    /// Per-stream `amount * acc_reward_per_share` at the last settlement, unscaled
+    pub reward_debts: [u128; MAX_REWARD_STREAMS],
+    /// Per-stream settled rewards not yet claimed
+    pub pending_rewards: [u64; MAX_REWARD_STREAMS],
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl UserStake {
+    /// Space required for the UserStake account
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 16 * MAX_REWARD_STREAMS + 8 * MAX_REWARD_STREAMS + 1;
+    
+    /// Move rewards earned since the last settlement into `pending_rewards`
+    pub fn settle(&mut self, farm: &Farm) -> Result<()> {
+        for (i, stream) in farm.reward_streams.iter().enumerate() {
+            let earned = get_pending_reward(self.amount, stream.acc_reward_per_share, self.reward_debts[i])?;
+            self.pending_rewards[i] = self.pending_rewards[i]
+                .checked_add(earned)
+                .ok_or(AmmError::MathOverflow)?;
+        }
+        Ok(())
+    }
+    
+    /// Reset the reward checkpoints after `amount` changes
+    pub fn checkpoint(&mut self, farm: &Farm) -> Result<()> {
+        for (i, stream) in farm.reward_streams.iter().enumerate() {
+            self.reward_debts[i] = get_reward_debt(self.amount, stream.acc_reward_per_share)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,238 @@
+//! Property tests for the pool math the program runs on-chain.
+//!
+//! Inputs span the whole u64 domain. Where a result cannot fit its type the
+//! math must fail with `MathOverflow` rather than wrap or panic, and where it
+//! fits the invariants below must hold.
+
+use amm::error::AmmError;
+use amm::math::{get_amount_out, get_deposit_amounts, get_withdraw_amounts, integer_sqrt, quote, U256};
+use anchor_lang::prelude::Result;
+use proptest::prelude::*;
+
+/// Nonzero amounts of every bit width up to u64::MAX, so both small pools and
+/// amounts whose products overflow are drawn often
+fn amount() -> impl Strategy<Value = u64> {
+    (1u32..=64).prop_flat_map(|bits| 1u64..=u64::MAX >> (64 - bits))
+}
+
+fn is_overflow<T>(result: &Result<T>) -> bool {
+    matches!(result, Err(error) if *error == AmmError::MathOverflow.into())
+}
+
+fn is_trimmed_to_nothing<T>(result: &Result<T>) -> bool {
+    matches!(result, Err(error) if *error == AmmError::ZeroLiquidity.into())
+}
+
+fn is_insufficient<T>(result: &Result<T>) -> bool {
+    matches!(result, Err(error) if *error == AmmError::InsufficientLiquidity.into())
+}
+
+/// Whether `amount * numerator / denominator` exceeds u64
+fn mul_div_overflows(amount: u64, numerator: u64, denominator: u64) -> bool {
+    amount as u128 * numerator as u128 / denominator as u128 > u64::MAX as u128
+}
+
+/// The amounts a later deposit takes, in exact arithmetic: all of the side
+/// that binds and the ratio-matched amount of the other
+fn trimmed_deposit(amount_a_max: u64, amount_b_max: u64, reserve_a: u64, reserve_b: u64) -> (u64, u64) {
+    let amount_b_optimal = amount_a_max as u128 * reserve_b as u128 / reserve_a as u128;
+    if amount_b_optimal <= amount_b_max as u128 {
+        (amount_a_max, amount_b_optimal as u64)
+    } else {
+        ((amount_b_max as u128 * reserve_a as u128 / reserve_b as u128) as u64, amount_b_max)
+    }
+}
+
+/// Whether `get_deposit_amounts` has to fail with `MathOverflow`. The trimmed
+/// amounts always fit, so only the LP tokens minted can overflow.
+fn deposit_overflows(
+    amount_a_max: u64,
+    amount_b_max: u64,
+    reserve_a: u64,
+    reserve_b: u64,
+    lp_supply: u64,
+) -> bool {
+    let (amount_a, amount_b) = trimmed_deposit(amount_a_max, amount_b_max, reserve_a, reserve_b);
+    mul_div_overflows(amount_a, lp_supply, reserve_a) && mul_div_overflows(amount_b, lp_supply, reserve_b)
+}
+
+proptest! {
+    #[test]
+    fn swap_never_decreases_k(
+        reserve_in in amount(),
+        reserve_out in amount(),
+        amount_in in amount(),
+        fee_bps in 0u16..=1_000,
+    ) {
+        let result = get_amount_out(amount_in, reserve_in, reserve_out, fee_bps);
+        
+        // The u128 numerator and denominator of the swap formula
+        let amount_in_with_fee = amount_in as u128 * (10_000 - fee_bps) as u128;
+        let fits = (reserve_out as u128).checked_mul(amount_in_with_fee)
+            .zip((reserve_in as u128 * 10_000).checked_add(amount_in_with_fee))
+            .is_some();
+        if !fits {
+            prop_assert!(is_overflow(&result));
+            return Ok(());
+        }
+        
+        let amount_out = result.unwrap();
+        prop_assert!(amount_out < reserve_out);
+        
+        let k_before = U256::from(reserve_in) * U256::from(reserve_out);
+        let k_after = (U256::from(reserve_in) + U256::from(amount_in)) * U256::from(reserve_out - amount_out);
+        prop_assert!(k_after >= k_before);
+    }
+    
+    #[test]
+    fn integer_sqrt_is_floor_sqrt(value in any::<u128>()) {
+        let root = integer_sqrt(value);
+        prop_assert!(root * root <= value);
+        prop_assert!((root + 1).checked_mul(root + 1).is_none_or(|square| square > value));
+    }
+    
+    #[test]
+    fn integer_sqrt_of_perfect_square(root in any::<u64>()) {
+        prop_assert_eq!(integer_sqrt(root as u128 * root as u128), root as u128);
+    }
+    
+    #[test]
+    fn quote_is_monotone(
+        amount_x in amount(),
+        amount_y in amount(),
+        reserve_a in amount(),
+        reserve_b in amount().prop_filter("room for one more", |reserve| *reserve < u64::MAX),
+    ) {
+        let (low, high) = (amount_x.min(amount_y), amount_x.max(amount_y));
+        let high_quote = quote(high, reserve_a, reserve_b);
+        if mul_div_overflows(high, reserve_b, reserve_a) {
+            prop_assert!(is_overflow(&high_quote));
+            return Ok(());
+        }
+        
+        let high_quote = high_quote.unwrap();
+        prop_assert!(quote(low, reserve_a, reserve_b).unwrap() <= high_quote);
+        
+        // And in the output-side reserve
+        let bigger_reserve = quote(high, reserve_a, reserve_b + 1);
+        if mul_div_overflows(high, reserve_b + 1, reserve_a) {
+            prop_assert!(is_overflow(&bigger_reserve));
+        } else {
+            prop_assert!(high_quote <= bigger_reserve.unwrap());
+        }
+    }
+    
+    #[test]
+    fn add_then_remove_returns_at_most_the_deposit(
+        reserve_a in amount(),
+        reserve_b in amount(),
+        lp_supply in amount(),
+        amount_a_max in amount(),
+        amount_b_max in amount(),
+    ) {
+        let deposit = get_deposit_amounts(amount_a_max, amount_b_max, 0, 0, reserve_a, reserve_b, lp_supply);
+        if deposit_overflows(amount_a_max, amount_b_max, reserve_a, reserve_b, lp_supply) {
+            prop_assert!(is_overflow(&deposit));
+            return Ok(());
+        }
+        
+        // Otherwise it only fails when trimmed to nothing
+        let Ok((amount_a, amount_b, lp)) = deposit else {
+            prop_assert!(is_trimmed_to_nothing(&deposit));
+            return Ok(());
+        };
+        if lp == 0 {
+            return Ok(());
+        }
+        prop_assert!(amount_a <= amount_a_max && amount_b <= amount_b_max);
+        
+        // Balances and supplies are u64 token amounts, so the pool cannot grow past them
+        let grown = reserve_a.checked_add(amount_a)
+            .zip(reserve_b.checked_add(amount_b))
+            .zip(lp_supply.checked_add(lp));
+        prop_assume!(grown.is_some());
+        let ((ra, rb), supply) = grown.unwrap();
+        
+        // A pro-rata share of the reserves always fits, and is refused only
+        // when it rounds to nothing
+        let withdraw = get_withdraw_amounts(lp, ra, rb, supply);
+        let Ok((out_a, out_b)) = withdraw else {
+            prop_assert!(is_insufficient(&withdraw));
+            return Ok(());
+        };
+        prop_assert!(out_a <= amount_a);
+        prop_assert!(out_b <= amount_b);
+    }
+    
+    #[test]
+    fn unbounded_maximum_takes_what_the_other_side_allows(
+        reserve_a in amount(),
+        reserve_b in amount(),
+        lp_supply in amount(),
+        amount_max in amount(),
+        a_is_unbounded in any::<bool>(),
+    ) {
+        // u64::MAX on one side means "whatever the other side allows", and is
+        // never priced against the reserves
+        let (amount_a_max, amount_b_max) = if a_is_unbounded { (u64::MAX, amount_max) } else { (amount_max, u64::MAX) };
+        let deposit = get_deposit_amounts(amount_a_max, amount_b_max, 0, 0, reserve_a, reserve_b, lp_supply);
+        if deposit_overflows(amount_a_max, amount_b_max, reserve_a, reserve_b, lp_supply) {
+            prop_assert!(is_overflow(&deposit));
+            return Ok(());
+        }
+        
+        let Ok((amount_a, amount_b, _)) = deposit else {
+            prop_assert!(is_trimmed_to_nothing(&deposit));
+            return Ok(());
+        };
+        prop_assert_eq!((amount_a, amount_b), trimmed_deposit(amount_a_max, amount_b_max, reserve_a, reserve_b));
+    }
+    
+    #[test]
+    fn first_deposit_round_trip_returns_at_most_the_deposit(
+        amount_a in amount(),
+        amount_b in amount(),
+    ) {
+        // sqrt(a * b) of two u64s always fits
+        let (amount_a, amount_b, lp) = get_deposit_amounts(amount_a, amount_b, 0, 0, 0, 0, 0).unwrap();
+        let (out_a, out_b) = get_withdraw_amounts(lp, amount_a, amount_b, lp).unwrap();
+        prop_assert!(out_a <= amount_a && out_b <= amount_b);
+    }
+    
+    #[test]
+    fn liquidity_changes_never_dilute_other_lps(
+        reserve_a in amount(),
+        reserve_b in amount(),
+        lp_supply in amount(),
+        amount_a_max in amount(),
+        amount_b_max in amount(),
+    ) {
+        // Reserves backing each LP token never shrink: r' / s' >= r / s
+        let not_diluted = |r: u64, s: u64, r_new: u64, s_new: u64| r_new as u128 * s as u128 >= r as u128 * s_new as u128;
+        
+        let deposit = get_deposit_amounts(amount_a_max, amount_b_max, 0, 0, reserve_a, reserve_b, lp_supply);
+        if deposit_overflows(amount_a_max, amount_b_max, reserve_a, reserve_b, lp_supply) {
+            prop_assert!(is_overflow(&deposit));
+            return Ok(());
+        }
+        let Ok((amount_a, amount_b, lp)) = deposit else {
+            prop_assert!(is_trimmed_to_nothing(&deposit));
+            return Ok(());
+        };
+        let grown = reserve_a.checked_add(amount_a)
+            .zip(reserve_b.checked_add(amount_b))
+            .zip(lp_supply.checked_add(lp));
+        prop_assume!(grown.is_some());
+        let ((ra, rb), supply) = grown.unwrap();
+        prop_assert!(not_diluted(reserve_a, lp_supply, ra, supply));
+        prop_assert!(not_diluted(reserve_b, lp_supply, rb, supply));
+        
+        let withdraw = get_withdraw_amounts(lp, ra, rb, supply);
+        let Ok((out_a, out_b)) = withdraw else {
+            prop_assert!(is_insufficient(&withdraw));
+            return Ok(());
+        };
+        prop_assert!(not_diluted(ra, supply, ra - out_a, lp_supply));
+        prop_assert!(not_diluted(rb, supply, rb - out_b, lp_supply));
+    }
+}